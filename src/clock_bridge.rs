@@ -0,0 +1,260 @@
+//! Bridges an external pulse-based clock onto a Link session's tempo and
+//! phase.
+//!
+//! [`ClockBridge`] turns a stream of `(pulse_index, Instant)` arrivals from
+//! an external master clock — MIDI beat clock, tap tempo, a hardware
+//! trigger — into [`SessionState`] tempo and phase updates, the way Ardour
+//! slaves its transport to an external source.
+
+use crate::{Duration, Instant, SessionState, MAX_BPM, MIN_BPM};
+
+/// Default smoothing factor for the inter-pulse period's exponential moving
+/// average.
+const DEFAULT_ALPHA: f64 = 0.1;
+
+/// Default tempo deadband, in BPM, below which a new tempo estimate is not
+/// pushed to the session.
+const DEFAULT_TEMPO_DEADBAND_BPM: f64 = 0.1;
+
+/// Period measurements longer than this factor times the current EMA are
+/// rejected as outliers (e.g. a dropped pulse).
+const OUTLIER_REJECTION_FACTOR: f64 = 2.0;
+
+/// How [`ClockBridge::align_phase`] applies phase corrections to the Link
+/// timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhaseAlignment {
+    /// Request the beat mapping via [`SessionState::request_beat_at_time`],
+    /// respecting other peers' session phase.
+    #[default]
+    Request,
+    /// Force the beat mapping via [`SessionState::force_beat_at_time`],
+    /// overriding session phase. Use this when the external clock is
+    /// authoritative and the Link session must follow it exactly.
+    Force,
+}
+
+/// Recovers tempo and phase from an external pulse stream and applies them
+/// to a [`SessionState`].
+///
+/// # Usage
+///
+/// Call [`pulse`](Self::pulse) on every incoming pulse (e.g. every MIDI beat
+/// clock tick) with a captured session state and a commit callback — the
+/// bridge estimates and smooths the inter-pulse period, and
+/// pushes it via [`SessionState::set_tempo`] once it drifts beyond a
+/// deadband. Call [`align_phase`](Self::align_phase) periodically (it need
+/// not be every pulse) to align the timeline's phase to the external pulse
+/// count.
+///
+/// Because it only ever touches the [`SessionState`] passed in, the same
+/// bridge works from both the application and audio threads:
+///
+/// ```no_run
+/// use esp_idf_ableton_link::{ClockBridge, Link};
+///
+/// let mut link = Link::new(120.0).unwrap();
+/// let mut bridge = ClockBridge::new(24.0, 4.0); // 24 PPQN MIDI beat clock
+///
+/// // On each incoming MIDI clock pulse:
+/// let pulse_index = 0; // running count of pulses received
+/// let mut state = link.capture_app_session_state().unwrap();
+/// bridge.pulse(pulse_index, link.clock_now(), &mut state, |state| {
+///     link.commit_app_session_state(state);
+/// });
+/// ```
+pub struct ClockBridge {
+    pulses_per_beat: f64,
+    quantum: f64,
+    alpha: f64,
+    tempo_deadband_bpm: f64,
+    phase_alignment: PhaseAlignment,
+    last_pulse: Option<(u64, Instant)>,
+    period_ema: Option<Duration>,
+    last_committed_bpm: Option<f64>,
+}
+
+impl ClockBridge {
+    /// Create a new bridge for an external clock with the given resolution.
+    ///
+    /// # Arguments
+    ///
+    /// * `pulses_per_beat` - Pulses per beat of the external clock (e.g.
+    ///   `24.0` for MIDI beat clock).
+    /// * `quantum` - The quantum (beats per cycle/bar) used for phase
+    ///   alignment.
+    #[must_use]
+    pub fn new(pulses_per_beat: f64, quantum: f64) -> Self {
+        Self {
+            pulses_per_beat,
+            quantum,
+            alpha: DEFAULT_ALPHA,
+            tempo_deadband_bpm: DEFAULT_TEMPO_DEADBAND_BPM,
+            phase_alignment: PhaseAlignment::Request,
+            last_pulse: None,
+            period_ema: None,
+            last_committed_bpm: None,
+        }
+    }
+
+    /// Set the period EMA's smoothing factor (default `0.1`). Higher values
+    /// track the external clock more closely but reject less jitter.
+    #[must_use]
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Set the tempo deadband in BPM (default `0.1`): a new tempo estimate
+    /// is only pushed to the session once it drifts from the last committed
+    /// value by more than this, to avoid thrashing peers.
+    #[must_use]
+    pub fn with_tempo_deadband_bpm(mut self, bpm: f64) -> Self {
+        self.tempo_deadband_bpm = bpm;
+        self
+    }
+
+    /// Set how [`align_phase`](Self::align_phase) applies phase corrections
+    /// (default [`PhaseAlignment::Request`]).
+    #[must_use]
+    pub fn with_phase_alignment(mut self, mode: PhaseAlignment) -> Self {
+        self.phase_alignment = mode;
+        self
+    }
+
+    /// Returns `true` if more than `timeout` has elapsed since the last
+    /// pulse, indicating the external clock may have stalled.
+    ///
+    /// Callers should [`reset`](Self::reset) the bridge before resuming, so
+    /// the gap isn't mistaken for a single, very slow pulse.
+    #[must_use]
+    pub fn is_stalled(&self, now: Instant, timeout: Duration) -> bool {
+        self.last_pulse
+            .is_some_and(|(_, last_time)| now - last_time > timeout)
+    }
+
+    /// Discard pulse history, e.g. after a detected stall or a clock source
+    /// change. The next pulse is treated as the first.
+    pub fn reset(&mut self) {
+        self.last_pulse = None;
+        self.period_ema = None;
+        self.last_committed_bpm = None;
+    }
+
+    /// Feed a pulse from the external clock.
+    ///
+    /// `pulse_index` is a running count of pulses received, used to detect
+    /// dropped pulses between calls. At least two pulses are required before
+    /// a tempo estimate can be produced; `commit` is only invoked once the
+    /// smoothed tempo drifts beyond the deadband.
+    ///
+    /// # Arguments
+    ///
+    /// * `pulse_index` - The running pulse count.
+    /// * `time` - The time the pulse arrived, from [`Link::clock_now`](crate::Link::clock_now).
+    /// * `session` - The captured session state to update.
+    /// * `commit` - Called with `session` once an updated tempo should be
+    ///   committed (e.g. via [`Link::commit_app_session_state`](crate::Link::commit_app_session_state)
+    ///   or [`AudioLink::commit_session_state`](crate::AudioLink::commit_session_state)).
+    pub fn pulse(
+        &mut self,
+        pulse_index: u64,
+        time: Instant,
+        session: &mut SessionState,
+        commit: impl FnOnce(&SessionState),
+    ) {
+        let Some((last_index, last_time)) = self.last_pulse.replace((pulse_index, time)) else {
+            return;
+        };
+
+        let elapsed_pulses = pulse_index.saturating_sub(last_index);
+        if elapsed_pulses == 0 {
+            return;
+        }
+        let measured = (time - last_time) / elapsed_pulses.cast_signed();
+
+        // A zero or negative period means a duplicate or out-of-order pulse
+        // timestamp (plausible with coarse embedded clock resolution): it
+        // would otherwise divide through to an infinite or negative BPM
+        // below. Ignore the measurement rather than feeding it into the EMA.
+        if measured <= Duration::ZERO {
+            return;
+        }
+
+        let Some(period_ema) = self.period_ema else {
+            self.period_ema = Some(measured);
+            self.commit_tempo(time, session, commit);
+            return;
+        };
+
+        let measured_us = measured.as_micros() as f64;
+        let ema_us = period_ema.as_micros() as f64;
+
+        if measured_us.abs() > ema_us.abs() * OUTLIER_REJECTION_FACTOR {
+            // Outlier, likely a dropped or spurious pulse: ignore the
+            // measurement but keep the existing EMA.
+            return;
+        }
+
+        let smoothed_us = ema_us + self.alpha * (measured_us - ema_us);
+        self.period_ema = Some(Duration::from_micros(smoothed_us as i64));
+
+        self.commit_tempo(time, session, commit);
+    }
+
+    /// Align the Link timeline's phase to the external clock's pulse count.
+    ///
+    /// Call this periodically — it need not be on every pulse — once tempo
+    /// has stabilized. `pulse_index` is converted to a beat via
+    /// `pulses_per_beat` and mapped onto the timeline at `time`, using
+    /// [`SessionState::request_beat_at_time`] or
+    /// [`SessionState::force_beat_at_time`] depending on
+    /// [`with_phase_alignment`](Self::with_phase_alignment).
+    pub fn align_phase(
+        &self,
+        pulse_index: u64,
+        time: Instant,
+        session: &mut SessionState,
+        commit: impl FnOnce(&SessionState),
+    ) {
+        let beat = pulse_index.cast_signed() as f64 / self.pulses_per_beat;
+        match self.phase_alignment {
+            PhaseAlignment::Request => session.request_beat_at_time(beat, time, self.quantum),
+            PhaseAlignment::Force => session.force_beat_at_time(beat, time, self.quantum),
+        }
+        commit(session);
+    }
+
+    fn commit_tempo(
+        &mut self,
+        time: Instant,
+        session: &mut SessionState,
+        commit: impl FnOnce(&SessionState),
+    ) {
+        let Some(period_ema) = self.period_ema else {
+            return;
+        };
+        let period_secs = period_ema.as_micros() as f64 / 1_000_000.0;
+        let bpm = 60.0 * self.pulses_per_beat / period_secs;
+
+        // Guard against a non-finite estimate (e.g. a near-zero period
+        // surviving rounding) and clamp to the same legal range
+        // `TempoRamp::bpm_at` uses, rather than pushing something Link's
+        // session state was never meant to see straight into the FFI call.
+        if !bpm.is_finite() {
+            return;
+        }
+        let bpm = bpm.clamp(MIN_BPM, MAX_BPM);
+
+        if self
+            .last_committed_bpm
+            .is_some_and(|last| (bpm - last).abs() < self.tempo_deadband_bpm)
+        {
+            return;
+        }
+
+        session.set_tempo(bpm, time);
+        self.last_committed_bpm = Some(bpm);
+        commit(session);
+    }
+}
@@ -0,0 +1,203 @@
+//! An event queue so the audio thread can observe Link changes without
+//! taking the `set_*_callback` mutex.
+//!
+//! [`Link::enable_event_queue`] wires up raw C trampolines that push
+//! [`LinkEvent`]s into a fixed-capacity ring buffer; [`AudioLink::poll_event`](crate::AudioLink::poll_event)
+//! drains it from the audio thread with no locking and no allocation. The
+//! trampolines themselves are serialized with a lock into a single logical
+//! producer — see [`EventQueue`] — so only the consumer side is lock-free.
+
+use std::cell::UnsafeCell;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::TransportState;
+
+/// Default capacity of the event queue created by [`Link::new`](crate::Link::new).
+pub const DEFAULT_CAPACITY: usize = 32;
+
+/// An event observed from a Link session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinkEvent {
+    /// The number of connected peers changed.
+    PeerCount(u64),
+    /// The tempo changed, in Beats Per Minute.
+    Tempo(f64),
+    /// The transport state changed.
+    Transport(TransportState),
+}
+
+/// What [`EventQueue::push`] should do when the ring buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum OverflowPolicy {
+    /// Drop the incoming event, keeping the events already queued.
+    #[default]
+    DropNewest = 0,
+    /// Drop the oldest queued event to make room for the incoming one.
+    OverwriteOldest = 1,
+}
+
+impl OverflowPolicy {
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::OverwriteOldest,
+            _ => Self::DropNewest,
+        }
+    }
+}
+
+/// A fixed-capacity event queue: a wait-free consumer over a producer side
+/// serialized with a lock.
+///
+/// The producer side is the set of trampolines installed by
+/// [`Link::enable_event_queue`]: one each for peer count, tempo, and
+/// transport state, wired to independent C callbacks that Link can invoke
+/// concurrently from different internal threads (the baseline
+/// `set_*_callback` plumbing only mutex-protects each callback's own context
+/// — it does not serialize the three against each other). [`push`](Self::push)
+/// takes `push_lock` to make them a single logical producer before touching
+/// `tail`/`buf`. The consumer side is [`AudioLink::poll_event`](crate::AudioLink::poll_event),
+/// which must only be called from the audio thread bound via
+/// [`Link::bind_audio_thread`](crate::Link::bind_audio_thread), and never
+/// takes `push_lock` — it stays lock-free as documented at the module level.
+///
+/// `head` is read by both sides but only ever *advanced* by the consumer,
+/// with one exception: under [`OverflowPolicy::OverwriteOldest`] the
+/// producer also advances it, using a CAS so it can never clobber or race an
+/// advance the consumer makes concurrently in [`poll`](Self::poll) — see the
+/// comment in [`push`](Self::push).
+pub(crate) struct EventQueue<const N: usize = DEFAULT_CAPACITY> {
+    buf: [UnsafeCell<LinkEvent>; N],
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    policy: AtomicU8,
+    // Serializes the three `enable_event_queue` trampolines into a single
+    // logical producer. See `push`. Never taken by the consumer.
+    push_lock: Mutex<()>,
+}
+
+// Safety: access to `buf` is serialized by the head/tail protocol below: the
+// producer (holding `push_lock`) only ever writes the slot at `tail`, the
+// consumer only ever reads the slot at `head`, and the atomics with
+// Acquire/Release ordering ensure a written slot is visible before its
+// index is published.
+unsafe impl<const N: usize> Sync for EventQueue<N> {}
+
+impl<const N: usize> EventQueue<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            buf: std::array::from_fn(|_| UnsafeCell::new(LinkEvent::Tempo(0.0))),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            policy: AtomicU8::new(OverflowPolicy::default() as u8),
+            push_lock: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn set_policy(&self, policy: OverflowPolicy) {
+        self.policy.store(policy as u8, Ordering::Relaxed);
+    }
+
+    fn policy(&self) -> OverflowPolicy {
+        OverflowPolicy::from_u8(self.policy.load(Ordering::Relaxed))
+    }
+
+    /// Push an event onto the queue. Called from the producer side
+    /// (Link-managed callback threads) — see the type docs for why this
+    /// takes `push_lock` despite the module being otherwise lock-free.
+    fn push(&self, event: LinkEvent) {
+        // `enable_event_queue` installs three independent trampolines that
+        // can fire concurrently from different Link-managed threads; without
+        // this lock, two of them racing here would both read/write `tail`
+        // and `buf` unsynchronized, a data race. The audio-thread consumer
+        // in `poll` never takes this lock, so it stays wait-free.
+        let _guard = self.push_lock.lock().unwrap();
+
+        let tail = self.tail.load(Ordering::Relaxed);
+        let next = (tail + 1) % N;
+
+        if next == self.head.load(Ordering::Acquire) {
+            match self.policy() {
+                OverflowPolicy::DropNewest => return,
+                OverflowPolicy::OverwriteOldest => {
+                    // `head` is otherwise owned exclusively by the consumer
+                    // (see `poll`'s doc comment), so we can't just load it
+                    // and blindly store `head + 1` here: a concurrent `poll`
+                    // could advance `head` itself between our load and
+                    // store, and our store would then either race that
+                    // advance or clobber it, over-advancing `head` and
+                    // silently dropping an extra live event. Instead, claim
+                    // exactly one slot with a CAS. If it fails because the
+                    // consumer got there first, re-read `head` and recheck
+                    // whether the queue is still full before trying again —
+                    // the consumer may have already freed the room we need.
+                    let mut head = self.head.load(Ordering::Acquire);
+                    while next == head {
+                        match self.head.compare_exchange_weak(
+                            head,
+                            (head + 1) % N,
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                        ) {
+                            Ok(_) => break,
+                            Err(current) => head = current,
+                        }
+                    }
+                }
+            }
+        }
+
+        // Safety: only the single producer ever writes `buf[tail]`, and the
+        // consumer only reads a slot after observing its index via the
+        // Acquire load of `tail` above/below, so there is no concurrent
+        // access to this slot.
+        unsafe { *self.buf[tail].get() = event };
+        self.tail.store(next, Ordering::Release);
+    }
+
+    /// Pop the oldest queued event, if any. Must only be called from the
+    /// single consumer thread.
+    pub(crate) fn poll(&self) -> Option<LinkEvent> {
+        let head = self.head.load(Ordering::Relaxed);
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+
+        // Safety: see push.
+        let event = unsafe { *self.buf[head].get() };
+        self.head.store((head + 1) % N, Ordering::Release);
+        Some(event)
+    }
+}
+
+// Raw C trampolines for the event queue producer side. Unlike the generic
+// `trampoline<T>` in lib.rs, these never take a callback's `Mutex`:
+// `context` points directly at the `Link`'s `EventQueue`, and the only lock
+// involved is `push_lock`, taken inside `push`.
+
+pub(crate) extern "C" fn trampoline_peer_count(value: u64, context: *mut c_void) {
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        // Safety: context is a pointer to the `EventQueue` owned by the
+        // `Link` that installed this trampoline, which outlives it.
+        let queue = unsafe { &*context.cast::<EventQueue>() };
+        queue.push(LinkEvent::PeerCount(value));
+    }));
+}
+
+pub(crate) extern "C" fn trampoline_tempo(value: f64, context: *mut c_void) {
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        // Safety: see trampoline_peer_count.
+        let queue = unsafe { &*context.cast::<EventQueue>() };
+        queue.push(LinkEvent::Tempo(value));
+    }));
+}
+
+pub(crate) extern "C" fn trampoline_transport(value: bool, context: *mut c_void) {
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        // Safety: see trampoline_peer_count.
+        let queue = unsafe { &*context.cast::<EventQueue>() };
+        queue.push(LinkEvent::Transport(value.into()));
+    }));
+}
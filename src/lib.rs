@@ -347,16 +347,32 @@
 //! clarity, since the state can be either currently active or scheduled for the
 //! future (see [The Transport State Model](#the-transport-state-model)).
 
-use std::{
-    ffi::c_void,
-    marker::PhantomData,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
-    sync::Mutex,
-    time::Duration as StdDuration,
-};
+use std::{ffi::c_void, marker::PhantomData, sync::Mutex};
 
 use delegate::delegate;
 
+mod clock_bridge;
+mod clock_output;
+mod events;
+pub mod midi;
+mod position;
+mod scheduler;
+mod session;
+mod streams;
+mod tempo_ramp;
+mod time;
+mod timeline;
+
+pub use clock_bridge::{ClockBridge, PhaseAlignment};
+pub use clock_output::{ClockOutput, Pulses};
+pub use events::{LinkEvent, OverflowPolicy};
+pub use position::BarBeatTick;
+pub use scheduler::ScheduledEvent;
+pub use session::{BeatsInBuffer, SessionState};
+pub use tempo_ramp::{Easing, TempoRamp, MAX_BPM, MIN_BPM};
+pub use time::{Duration, Instant};
+pub use timeline::Timeline;
+
 type Callback<T> = Mutex<Option<Box<dyn FnMut(T) + Send>>>;
 
 /// The transport state: [`Play`](Self::Play) or [`Stop`](Self::Stop).
@@ -366,6 +382,7 @@ type Callback<T> = Mutex<Option<Box<dyn FnMut(T) + Send>>>;
 /// section in the module documentation for details on interpreting transport
 /// state with [`SessionState::transport_state_time`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TransportState {
     /// Transport is playing or scheduled to play.
     Play,
@@ -389,327 +406,32 @@ impl From<TransportState> for bool {
     }
 }
 
-/// A point in time on the Link clock, measured in microseconds.
-///
-/// `Instant` represents an absolute timestamp from Link's internal clock,
-/// which is synchronized across all connected peers. It is analogous to
-/// [`std::time::Instant`](https://doc.rust-lang.org/std/time/struct.Instant.html) but specific to the Link clock domain.
-///
-/// # Creating `Instant` values
-///
-/// You typically obtain an `Instant` from [`Link::clock_now`] or
-/// [`SessionState::transport_state_time`]:
-///
-/// ```no_run
-/// use esp_idf_ableton_link::Link;
-///
-/// let link = Link::new(120.0).unwrap();
-/// let now = link.clock_now();
-/// ```
-///
-/// # Arithmetic
-///
-/// `Instant` supports addition and subtraction with [`Duration`]:
-///
-/// ```no_run
-/// use esp_idf_ableton_link::{Link, Duration};
-///
-/// let link = Link::new(120.0).unwrap();
-/// let now = link.clock_now();
-/// let later = now + Duration::from_millis(100);
-/// let earlier = now - Duration::from_millis(50);
-/// ```
-///
-/// Subtracting two `Instant` values yields a [`Duration`]:
-///
-/// ```no_run
-/// use esp_idf_ableton_link::Link;
-///
-/// let link = Link::new(120.0).unwrap();
-/// let t1 = link.clock_now();
-/// // ... some time passes ...
-/// let t2 = link.clock_now();
-/// let elapsed = t2 - t1; // Duration
-/// ```
-///
-/// For convenience, [`std::time::Duration`](https://doc.rust-lang.org/std/time/struct.Duration.html) is also supported:
-///
-/// ```no_run
-/// use esp_idf_ableton_link::Link;
-/// use std::time::Duration;
-///
-/// let link = Link::new(120.0).unwrap();
-/// let now = link.clock_now();
-/// let later = now + Duration::from_millis(100);
-/// ```
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Instant(i64);
-
-impl Instant {
-    /// Create an `Instant` from microseconds.
-    #[must_use]
-    pub(crate) const fn from_micros(micros: i64) -> Self {
-        Self(micros)
-    }
-
-    /// Get the time value as microseconds (signed).
-    #[must_use]
-    pub(crate) const fn as_micros(self) -> i64 {
-        self.0
-    }
-
-    /// Get the time value as an unsigned 64-bit integer (microseconds).
-    ///
-    /// This performs a bit-preserving cast. Link's clock is based on
-    /// `steady_clock` which always returns non-negative values, so this
-    /// is safe for normal use.
-    #[must_use]
-    pub(crate) const fn as_u64(self) -> u64 {
-        self.0.cast_unsigned()
-    }
-
-    /// Add microseconds to this time.
-    #[must_use]
-    pub const fn add_micros(self, micros: i64) -> Self {
-        Self(self.0 + micros)
-    }
-
-    /// Subtract microseconds from this time.
-    #[must_use]
-    pub const fn sub_micros(self, micros: i64) -> Self {
-        Self(self.0 - micros)
-    }
-
-    /// Add milliseconds to this time.
-    #[must_use]
-    pub const fn add_millis(self, millis: i64) -> Self {
-        Self(self.0 + millis * 1_000)
-    }
-
-    /// Subtract milliseconds from this time.
-    #[must_use]
-    pub const fn sub_millis(self, millis: i64) -> Self {
-        Self(self.0 - millis * 1_000)
-    }
-
-    /// Add seconds to this time.
-    #[must_use]
-    pub const fn add_secs(self, secs: i64) -> Self {
-        Self(self.0 + secs * 1_000_000)
-    }
-
-    /// Subtract seconds from this time.
-    #[must_use]
-    pub const fn sub_secs(self, secs: i64) -> Self {
-        Self(self.0 - secs * 1_000_000)
-    }
-}
-
-/// A duration of time in microseconds, for use with [`Instant`].
-///
-/// `Duration` is a lightweight alternative to [`std::time::Duration`](https://doc.rust-lang.org/std/time/struct.Duration.html) that
-/// avoids the overhead of nanosecond precision and `u128` arithmetic on
-/// embedded systems. Unlike `std::time::Duration`, this type supports
-/// **signed** values, allowing representation of negative durations.
-///
-/// # Creating `Duration` values
-///
-/// ```no_run
-/// use esp_idf_ableton_link::Duration;
-///
-/// let d1 = Duration::from_micros(500);
-/// let d2 = Duration::from_millis(10);
-/// let d3 = Duration::from_secs(1);
-/// ```
-///
-/// # Arithmetic
-///
-/// `Duration` supports multiplication and division by `i64`:
-///
-/// ```no_run
-/// use esp_idf_ableton_link::Duration;
-///
-/// let d = Duration::from_millis(100);
-/// let doubled = d * 2;
-/// let halved = d / 2;
-/// ```
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Duration(i64);
-
-impl Duration {
-    /// A duration of zero.
-    pub const ZERO: Self = Self(0);
-
-    /// Create a `Duration` from microseconds.
-    #[must_use]
-    pub const fn from_micros(micros: i64) -> Self {
-        Self(micros)
-    }
-
-    /// Create a `Duration` from milliseconds.
-    #[must_use]
-    pub const fn from_millis(millis: i64) -> Self {
-        Self(millis * 1_000)
-    }
-
-    /// Create a `Duration` from seconds.
-    #[must_use]
-    pub const fn from_secs(secs: i64) -> Self {
-        Self(secs * 1_000_000)
-    }
-
-    /// Get the duration as microseconds.
-    #[must_use]
-    pub const fn as_micros(self) -> i64 {
-        self.0
-    }
-
-    /// Get the duration as milliseconds (truncating).
-    #[must_use]
-    pub const fn as_millis(self) -> i64 {
-        self.0 / 1_000
-    }
-
-    /// Get the duration as seconds (truncating).
-    #[must_use]
-    pub const fn as_secs(self) -> i64 {
-        self.0 / 1_000_000
-    }
-
-    /// Returns the absolute value of this duration.
-    #[must_use]
-    pub const fn abs(self) -> Self {
-        Self(self.0.abs())
-    }
-}
-
-impl Add for Duration {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0)
-    }
-}
-
-impl AddAssign for Duration {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs;
-    }
-}
-
-impl Sub for Duration {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0)
-    }
-}
-
-impl SubAssign for Duration {
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = *self - rhs;
-    }
-}
-
-impl Mul<i64> for Duration {
-    type Output = Self;
-
-    fn mul(self, rhs: i64) -> Self::Output {
-        Self(self.0 * rhs)
-    }
-}
-
-impl MulAssign<i64> for Duration {
-    fn mul_assign(&mut self, rhs: i64) {
-        *self = *self * rhs;
-    }
-}
-
-impl Div<i64> for Duration {
-    type Output = Self;
-
-    fn div(self, rhs: i64) -> Self::Output {
-        Self(self.0 / rhs)
-    }
-}
-
-impl DivAssign<i64> for Duration {
-    fn div_assign(&mut self, rhs: i64) {
-        *self = *self / rhs;
-    }
-}
-
-impl Add<Duration> for Instant {
-    type Output = Self;
-
-    fn add(self, rhs: Duration) -> Self::Output {
-        Self(self.0 + rhs.0)
-    }
-}
-
-impl AddAssign<Duration> for Instant {
-    fn add_assign(&mut self, rhs: Duration) {
-        *self = *self + rhs;
-    }
-}
-
-impl Sub<Duration> for Instant {
-    type Output = Self;
-
-    fn sub(self, rhs: Duration) -> Self::Output {
-        Self(self.0 - rhs.0)
-    }
-}
-
-impl SubAssign<Duration> for Instant {
-    fn sub_assign(&mut self, rhs: Duration) {
-        *self = *self - rhs;
-    }
-}
-
-impl Sub<Instant> for Instant {
-    type Output = Duration;
-
-    fn sub(self, rhs: Instant) -> Self::Output {
-        Duration(self.0 - rhs.0)
-    }
-}
-
-impl Add<StdDuration> for Instant {
-    type Output = Self;
-
-    fn add(self, rhs: StdDuration) -> Self::Output {
-        let micros = i64::try_from(rhs.as_micros()).unwrap_or(i64::MAX);
-        Self(self.0 + micros)
-    }
-}
-
-impl AddAssign<StdDuration> for Instant {
-    fn add_assign(&mut self, rhs: StdDuration) {
-        *self = *self + rhs;
-    }
-}
-
-impl Sub<StdDuration> for Instant {
-    type Output = Self;
-
-    fn sub(self, rhs: StdDuration) -> Self::Output {
-        let micros = i64::try_from(rhs.as_micros()).unwrap_or(i64::MAX);
-        Self(self.0 - micros)
-    }
-}
-
-impl SubAssign<StdDuration> for Instant {
-    fn sub_assign(&mut self, rhs: StdDuration) {
-        *self = *self - rhs;
-    }
-}
-
 mod sys {
     // Allow wildcard imports for the sys module since there is nothing else in
     // this module.
     #[allow(clippy::wildcard_imports)]
     pub use esp_idf_sys::abl_link::*;
+
+    // `abl_link_create_with_clock` does not exist yet: the generated
+    // bindings above come from the fixed `abl_link` C API, which always
+    // instantiates `ableton::Link` against Link's default platform clock.
+    // Supporting a caller-supplied clock (see `Link::with_clock`) means
+    // instantiating the upstream C++ `BasicLink<ClockAdapter>` template
+    // instead, which requires extending the `abl_link` C shim itself
+    // (outside this crate, in the esp-idf-sys `extra_components` it
+    // vendors) with a constructor that takes a clock callback. This
+    // declaration documents the shape that extension needs to have so
+    // `Link::with_clock` below can link once it lands upstream. Gated behind
+    // the `unstable-clock-binding` feature (off by default) so it can't be
+    // linked into a real build before that shim exists.
+    #[cfg(feature = "unstable-clock-binding")]
+    extern "C" {
+        pub fn abl_link_create_with_clock(
+            initial_bpm: f64,
+            clock_fn: Option<extern "C" fn(context: *mut std::ffi::c_void) -> u64>,
+            clock_context: *mut std::ffi::c_void,
+        ) -> abl_link;
+    }
 }
 
 /// Error type for Link operations.
@@ -717,12 +439,15 @@ mod sys {
 pub enum LinkError {
     /// Failed to allocate memory.
     AllocationFailed,
+    /// An ESP-IDF `esp_timer` operation failed, with the raw `esp_err_t`.
+    TimerError(esp_idf_sys::esp_err_t),
 }
 
 impl std::fmt::Display for LinkError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::AllocationFailed => write!(f, "Failed to allocate memory"),
+            Self::TimerError(err) => write!(f, "ESP-IDF timer operation failed (esp_err_t {err})"),
         }
     }
 }
@@ -744,6 +469,33 @@ extern "C" fn trampoline<T>(value: T, context: *mut c_void) {
     }));
 }
 
+// Holds the user's clock closure for a `Link` created via `Link::with_clock`.
+// Boxing it in this sized wrapper (rather than passing a `Box<dyn Fn>`
+// directly) keeps its heap address stable and a thin pointer, the same
+// trick `scheduler::ScheduleState` uses: the C side only ever sees a raw
+// pointer to this struct, not to the fat `dyn Fn` pointer inside it.
+//
+// Gated behind `unstable-clock-binding`; see `Link::with_clock`.
+#[cfg(feature = "unstable-clock-binding")]
+struct ClockState {
+    clock_fn: Box<dyn Fn() -> u64 + Send + Sync>,
+}
+
+// Trampoline for the custom-clock FFI hook installed by `Link::with_clock`.
+// Unlike `trampoline<T>`, this has no internal Mutex to lock: the closure is
+// `Fn`, not `FnMut`, so concurrent calls from Link's internal threads are
+// safe without additional synchronization.
+#[cfg(feature = "unstable-clock-binding")]
+extern "C" fn clock_trampoline(context: *mut c_void) -> u64 {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        // Safety: context is a pointer to the `ClockState` stored in the
+        // `Link::clock_state` field, which outlives this trampoline.
+        let state = unsafe { &*context.cast::<ClockState>() };
+        (state.clock_fn)()
+    }))
+    .unwrap_or(0)
+}
+
 /// A safe wrapper around an Ableton Link instance.
 ///
 /// Link enables musical applications to synchronize tempo and beat phase over a
@@ -782,6 +534,17 @@ pub struct Link {
     num_peers_callback: Callback<u64>,
     tempo_callback: Callback<f64>,
     start_stop_callback: Callback<bool>,
+    // Opt-in lock-free event queue; see `enable_event_queue`. Always
+    // allocated (it's a fixed-size inline buffer, not a heap allocation) so
+    // `AudioLink::poll_event` can borrow it unconditionally.
+    event_queue: events::EventQueue,
+    // Keeps the user's clock closure alive for instances created via
+    // `with_clock`; `None` for instances using Link's default platform
+    // clock. The C side holds a raw pointer into this Box.
+    //
+    // Gated behind `unstable-clock-binding`; see `Link::with_clock`.
+    #[cfg(feature = "unstable-clock-binding")]
+    clock_state: Option<Box<ClockState>>,
 }
 
 // Safety: Link holds a pointer to a heap-allocated C++ object. All methods
@@ -832,6 +595,72 @@ impl Link {
                 num_peers_callback: Mutex::new(None),
                 tempo_callback: Mutex::new(None),
                 start_stop_callback: Mutex::new(None),
+                event_queue: events::EventQueue::new(),
+                #[cfg(feature = "unstable-clock-binding")]
+                clock_state: None,
+            })
+        }
+    }
+
+    /// Create a new Link instance whose timeline is driven by a
+    /// caller-supplied clock instead of Link's default platform clock.
+    ///
+    /// `clock_fn` is called to obtain the current time in microseconds, and
+    /// becomes the time source backing [`clock_now`](Self::clock_now) (and
+    /// therefore, transitively, every [`SessionState`] timestamp captured
+    /// from this `Link`). This is useful on ESP32 to reference Link's beat
+    /// timeline to a specific hardware timer domain — an `esp_timer` or an
+    /// I2S sample clock that drives your audio — instead of Link's default
+    /// clock, for sample-accurate beat alignment against the device's real
+    /// audio clock.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LinkError::AllocationFailed`] if the underlying C++ Link
+    /// instance could not be allocated.
+    ///
+    /// # Unimplemented: pending upstream
+    ///
+    /// This constructor is gated behind the `unstable-clock-binding`
+    /// feature, which is **off by default and not meant to be enabled**. It
+    /// binds to `abl_link_create_with_clock`, which does not exist in
+    /// esp-idf-sys's generated `abl_link` bindings — see the tracking note
+    /// next to its declaration in `mod sys` above — and will fail to link if
+    /// the feature is turned on before that shim function lands upstream.
+    /// The feature exists only so this series' intended shape for
+    /// caller-supplied clocks is visible in the source and can be turned on
+    /// once the upstream C shim is extended.
+    #[cfg(feature = "unstable-clock-binding")]
+    pub fn with_clock<F>(initial_bpm: f64, clock_fn: F) -> Result<Self, LinkError>
+    where
+        F: Fn() -> u64 + Send + Sync + 'static,
+    {
+        let state = Box::new(ClockState {
+            clock_fn: Box::new(clock_fn),
+        });
+        // `state` is a `Box<ClockState>`, a sized type, so this address is a
+        // thin pointer that stays valid for the state's lifetime regardless
+        // of how the `Link` we return is later moved.
+        let context = std::ptr::from_ref(&*state).cast_mut().cast::<c_void>();
+
+        // Safety: abl_link_create_with_clock is safe to call with any f64
+        // tempo and a valid clock_fn/context pair. `context` points at the
+        // heap allocation backing `state`, which we store below so it
+        // outlives the handle.
+        let handle = unsafe {
+            sys::abl_link_create_with_clock(initial_bpm, Some(clock_trampoline), context)
+        };
+
+        if handle.impl_.is_null() {
+            Err(LinkError::AllocationFailed)
+        } else {
+            Ok(Self {
+                handle,
+                num_peers_callback: Mutex::new(None),
+                tempo_callback: Mutex::new(None),
+                start_stop_callback: Mutex::new(None),
+                event_queue: events::EventQueue::new(),
+                clock_state: Some(state),
             })
         }
     }
@@ -900,6 +729,22 @@ impl Link {
         unsafe { sys::abl_link_num_peers(self.handle) }
     }
 
+    /// Check whether this session currently has any other peers connected.
+    ///
+    /// This distinguishes "syncing with peers" from "running solo," which
+    /// matters for [`request_beat_at_time`](SessionState::request_beat_at_time):
+    /// requests made while solo simply adjust the local timeline, while
+    /// requests made with peers connected are subject to negotiation with the
+    /// rest of the session.
+    ///
+    /// # Returns
+    ///
+    /// `true` if [`num_peers`](Self::num_peers) is greater than zero.
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        self.num_peers() > 0
+    }
+
     /// Check if transport synchronization is enabled.
     ///
     /// When enabled, transport start/stop state is shared with other peers
@@ -983,19 +828,12 @@ impl Link {
     /// let tempo = state.tempo();
     /// ```
     pub fn capture_app_session_state(&self) -> Result<SessionState, LinkError> {
-        // Safety: abl_link_create_session_state allocates a new session state.
-        let session_state = unsafe { sys::abl_link_create_session_state() };
-
-        if session_state.impl_.is_null() {
-            return Err(LinkError::AllocationFailed);
-        }
+        let session_state = SessionState::new()?;
 
         // Safety: Both handles are valid.
-        unsafe { sys::abl_link_capture_app_session_state(self.handle, session_state) };
+        unsafe { sys::abl_link_capture_app_session_state(self.handle, session_state.handle) };
 
-        Ok(SessionState {
-            handle: session_state,
-        })
+        Ok(session_state)
     }
 
     /// Commit the given session state to the Link session from an application
@@ -1249,6 +1087,62 @@ impl Link {
         *self.start_stop_callback.lock().unwrap() = None;
     }
 
+    /// Enable the lock-free event queue, drained from the audio thread via
+    /// [`AudioLink::poll_event`].
+    ///
+    /// The `set_*_callback` methods above route through a `Mutex`-guarded
+    /// [`Callback`], so any audio code reacting to peer/tempo/transport
+    /// changes today has to take a lock — a priority-inversion hazard on a
+    /// realtime task. The event queue is a fixed-capacity ring buffer
+    /// instead: peer count, tempo, and transport changes are pushed onto it
+    /// directly from Link's callback threads (serialized against each other
+    /// by a separate, non-realtime lock, since Link can invoke them
+    /// concurrently), and [`AudioLink::poll_event`] drains it from the
+    /// audio thread wait-free, with no mutex and no allocation.
+    ///
+    /// `policy` controls what happens when the queue is full: see
+    /// [`OverflowPolicy`].
+    ///
+    /// # Note
+    ///
+    /// The underlying C API allows only one callback per kind, so enabling
+    /// the event queue takes over the `abl_link_set_num_peers_callback`,
+    /// `abl_link_set_tempo_callback`, and `abl_link_set_start_stop_callback`
+    /// registrations — any callback previously installed via
+    /// [`set_num_peers_callback`](Self::set_num_peers_callback),
+    /// [`set_tempo_callback`](Self::set_tempo_callback), or
+    /// [`set_transport_state_callback`](Self::set_transport_state_callback)
+    /// is replaced. Calling those methods after this one will, in turn,
+    /// replace the event queue's registrations.
+    pub fn enable_event_queue(&self, policy: OverflowPolicy) {
+        self.event_queue.set_policy(policy);
+
+        let context = std::ptr::from_ref(&self.event_queue)
+            .cast_mut()
+            .cast::<c_void>();
+
+        // Safety: handle is valid (checked in new()). `context` points at
+        // `self.event_queue`, which is owned by this `Link` and outlives
+        // these registrations.
+        unsafe {
+            sys::abl_link_set_num_peers_callback(
+                self.handle,
+                Some(events::trampoline_peer_count),
+                context,
+            );
+            sys::abl_link_set_tempo_callback(
+                self.handle,
+                Some(events::trampoline_tempo),
+                context,
+            );
+            sys::abl_link_set_start_stop_callback(
+                self.handle,
+                Some(events::trampoline_transport),
+                context,
+            );
+        }
+    }
+
     /// Get the current Link clock time.
     ///
     /// This returns the current time from Link's internal clock, which is
@@ -1270,7 +1164,7 @@ impl Link {
     #[must_use]
     pub fn clock_now(&self) -> Instant {
         // Safety: handle is valid (checked in new()).
-        Instant(unsafe { sys::abl_link_clock_micros(self.handle) })
+        Instant::from_micros(unsafe { sys::abl_link_clock_micros(self.handle) })
     }
 
     /// Bind this Link instance for audio-thread access.
@@ -1401,28 +1295,38 @@ impl AudioLink<'_> {
     /// Capture the current Link session state (realtime-safe).
     ///
     /// This method is non-blocking and safe to call from a realtime audio
-    /// context. The returned [`SessionState`] is a snapshot that should be
-    /// used locally and not stored for later use.
+    /// context, but it still allocates a fresh [`SessionState`] on every
+    /// call. For a callback that runs on every audio block, prefer
+    /// [`capture_into`](Self::capture_into) with a `SessionState` allocated
+    /// once up front, to avoid allocating on the audio thread.
+    ///
+    /// The returned [`SessionState`] is a snapshot that should be used
+    /// locally and not stored for later use.
     ///
     /// # Errors
     ///
     /// Returns [`LinkError::AllocationFailed`] if the session state
     /// could not be allocated.
     pub fn capture_session_state(&self) -> Result<SessionState, LinkError> {
-        // Safety: abl_link_create_session_state allocates a new session state.
-        let session_state = unsafe { sys::abl_link_create_session_state() };
-
-        if session_state.impl_.is_null() {
-            return Err(LinkError::AllocationFailed);
-        }
-
+        let mut session_state = SessionState::new()?;
+        self.capture_into(&mut session_state);
+        Ok(session_state)
+    }
+
+    /// Capture the current Link session state into an existing, reused
+    /// `SessionState` (realtime-safe, zero-allocation).
+    ///
+    /// Unlike [`capture_session_state`](Self::capture_session_state), this
+    /// does not allocate: it overwrites `state` in place via
+    /// `abl_link_capture_audio_session_state`. Allocate a single scratch
+    /// `SessionState` with [`SessionState::new`] on the app thread once, then
+    /// call this every audio callback to refresh it with no heap activity,
+    /// matching Link's "realtime-safe: yes" contract for the audio capture
+    /// functions.
+    pub fn capture_into(&self, state: &mut SessionState) {
         // Safety: Both handles are valid. AudioLink's !Send guarantee ensures
         // we're on the designated audio thread.
-        unsafe { sys::abl_link_capture_audio_session_state(self.link.handle, session_state) };
-
-        Ok(SessionState {
-            handle: session_state,
-        })
+        unsafe { sys::abl_link_capture_audio_session_state(self.link.handle, state.handle) }
     }
 
     /// Commit the given session state to the Link session (realtime-safe).
@@ -1435,383 +1339,18 @@ impl AudioLink<'_> {
         // we're on the designated audio thread.
         unsafe { sys::abl_link_commit_audio_session_state(self.link.handle, state.handle) }
     }
-}
 
-/// A snapshot of the Link session state.
-///
-/// This represents a point-in-time view of the Link session's timeline and
-/// transport state. It provides methods to read and modify tempo, beat
-/// position, and transport (play/stop) state.
-///
-/// See the module documentation for background on:
-/// - [The Timeline](crate#the-timeline) — tempo, beats, phase, and quantum
-/// - [Transport State](crate#transport-state) — play/stop synchronization
-///
-/// # Usage
-///
-/// 1. Capture a session state with [`Link::capture_app_session_state`]
-///    or [`AudioLink::capture_session_state`]
-/// 2. Read values using [`tempo`](Self::tempo),
-///    [`beat_at_time`](Self::beat_at_time), [`transport_state`](Self::transport_state), etc.
-/// 3. Optionally modify using [`set_tempo`](Self::set_tempo),
-///    [`request_beat_at_time`](Self::request_beat_at_time),
-///    [`set_transport_state_at`](Self::set_transport_state_at), etc.
-/// 4. Commit changes with [`Link::commit_app_session_state`]
-///    or [`AudioLink::commit_session_state`]
-///
-/// # Important
-///
-/// This is a snapshot and will become stale. Don't store it for later use.
-/// Capture a fresh state when you need current values.
-pub struct SessionState {
-    handle: sys::abl_link_session_state,
-}
-
-// Safety: SessionState is an independent snapshot with no references to Link.
-// It can be safely moved between threads.
-//
-// Note: Sync is intentionally NOT implemented. The underlying C API does not
-// document thread-safety for concurrent reads of session state, and the design
-// intent is for session state to be used in a local scope after capture.
-unsafe impl Send for SessionState {}
-
-impl SessionState {
-    /// Get the tempo of the timeline in Beats Per Minute.
+    /// Pop the oldest queued [`LinkEvent`] (realtime-safe, wait-free,
+    /// lock-free).
     ///
-    /// This is a stable value appropriate for display to the user. Beat time
-    /// progress may not match this tempo exactly due to clock drift
-    /// compensation.
+    /// Returns `None` if no event is queued. The queue must first be enabled
+    /// with [`Link::enable_event_queue`]; otherwise this always returns
+    /// `None`. `AudioLink`'s `!Send` guarantee ensures this is always called
+    /// from the single bound audio thread, satisfying the event queue's
+    /// single-consumer requirement.
     #[must_use]
-    pub fn tempo(&self) -> f64 {
-        // Safety: handle is valid (checked in new()).
-        unsafe { sys::abl_link_tempo(self.handle) }
-    }
-
-    /// Set the timeline tempo to the given BPM value.
-    ///
-    /// The `time` parameter serves as the pivot point for the tempo change:
-    /// the beat value at this time is preserved, while beat values at all
-    /// other times are recalculated according to the new tempo. The tempo
-    /// change affects the entire timeline immediately upon commit, not
-    /// "starting at" the given time.
-    ///
-    /// Changes are local to this snapshot until committed with
-    /// [`Link::commit_app_session_state`] or [`AudioLink::commit_session_state`].
-    ///
-    /// # Arguments
-    ///
-    /// * `bpm` - The new tempo in beats per minute.
-    /// * `time` - The pivot point for the tempo change. The beat value at this
-    ///   time remains unchanged; beats at other times shift according to the
-    ///   new tempo.
-    pub fn set_tempo(&mut self, bpm: f64, time: Instant) {
-        // Safety: handle is valid (checked in new()).
-        unsafe { sys::abl_link_set_tempo(self.handle, bpm, time.as_micros()) }
-    }
-
-    /// Get the beat value at the given time for the given quantum.
-    ///
-    /// The beat value's magnitude is unique to this Link instance, but its
-    /// phase with respect to the quantum is shared among all session peers.
-    ///
-    /// # Arguments
-    ///
-    /// * `time` - The time (from [`Link::clock_now`]).
-    /// * `quantum` - The quantum (beats per cycle/bar).
-    #[must_use]
-    pub fn beat_at_time(&self, time: Instant, quantum: f64) -> f64 {
-        // Safety: handle is valid (checked in new()).
-        unsafe { sys::abl_link_beat_at_time(self.handle, time.as_micros(), quantum) }
-    }
-
-    /// Get the phase (position within a cycle) at the given time.
-    ///
-    /// The result is in the interval `[0, quantum)`. This is equivalent to
-    /// `beat_at_time(t, q) % q` for non-negative beat values, but handles
-    /// negative values correctly.
-    ///
-    /// # Arguments
-    ///
-    /// * `time` - The time (from [`Link::clock_now`]).
-    /// * `quantum` - The quantum (beats per cycle/bar).
-    #[must_use]
-    pub fn phase_at_time(&self, time: Instant, quantum: f64) -> f64 {
-        // Safety: handle is valid (checked in new()).
-        unsafe { sys::abl_link_phase_at_time(self.handle, time.as_micros(), quantum) }
-    }
-
-    /// Get the time at which the given beat occurs for the given quantum.
-    ///
-    /// This is the inverse of [`beat_at_time`](Self::beat_at_time), assuming
-    /// constant tempo.
-    ///
-    /// # Arguments
-    ///
-    /// * `beat` - The beat value.
-    /// * `quantum` - The quantum (beats per cycle/bar).
-    #[must_use]
-    pub fn time_at_beat(&self, beat: f64, quantum: f64) -> Instant {
-        // Safety: handle is valid (checked in new()).
-        Instant(unsafe { sys::abl_link_time_at_beat(self.handle, beat, quantum) })
-    }
-
-    /// Request a beat/time mapping, respecting session phase when not alone
-    /// in the session (quantized launch).
-    ///
-    /// This only changes the local beat/time mapping; it does not affect other
-    /// peers' beat magnitudes.
-    ///
-    /// # Behavior
-    ///
-    /// - **When alone** (no other peers): The beat is mapped to `at_time`.
-    ///   After committing, `beat_at_time(at_time, quantum) == beat`.
-    ///
-    /// - **When not alone**: To avoid disrupting the session, the beat is
-    ///   mapped to the first time **≥ `at_time`** where the session phase
-    ///   matches the phase of `beat`. This enables "quantized launch" where
-    ///   events happen in-phase with the session.
-    ///
-    /// # When Does the Timeline Shift?
-    ///
-    /// The timeline shifts **immediately upon commit**, not at `at_time`. The
-    /// `at_time` parameter specifies which point on the timeline should have
-    /// the given beat value—the entire timeline shifts to satisfy this
-    /// constraint. This means `beat_at_time()` will return different values
-    /// for **all** times (past, present, and future) after committing.
-    ///
-    /// For example, if you map beat `0.0` to a time 2 beats in the future,
-    /// the current beat becomes `-2.0`. Negative beats are valid and represent
-    /// a "count-in" before beat zero.
-    ///
-    /// # Example
-    ///
-    /// With quantum `4.0`, if the session is currently at phase `2.5` and you
-    /// request beat `0.0` (phase `0.0`) at the current time:
-    /// - When alone: beat `0.0` is mapped to now immediately.
-    /// - When not alone: beat `0.0` is mapped to the next downbeat (when
-    ///   session phase reaches `0.0`), which is 1.5 beats in the future.
-    ///   The current beat becomes `-1.5`.
-    ///
-    /// # Arguments
-    ///
-    /// * `beat` - The beat to map (only affects local magnitude, not session phase).
-    /// * `time` - The earliest time for the mapping (actual time may be later
-    ///   when not alone in the session).
-    /// * `quantum` - The quantum (beats per cycle/bar).
-    ///
-    /// Changes are local to this snapshot until committed with
-    /// [`Link::commit_app_session_state`] or [`AudioLink::commit_session_state`].
-    pub fn request_beat_at_time(&mut self, beat: f64, time: Instant, quantum: f64) {
-        // Safety: handle is valid (checked in new()).
-        unsafe {
-            sys::abl_link_request_beat_at_time(self.handle, beat, time.as_micros(), quantum);
-        }
-    }
-
-    /// Forcibly shift the session phase, affecting all peers.
-    ///
-    /// Unlike [`request_beat_at_time`](Self::request_beat_at_time), this does not
-    /// wait for phase alignment when other peers are connected. It shifts the
-    /// session phase to match the requested beat's phase at the given time.
-    ///
-    /// # Effect on Other Peers
-    ///
-    /// Other peers' beat magnitudes are adjusted by the phase shift amount to
-    /// keep everyone synchronized at the new phase. For example, with quantum
-    /// `4.0`:
-    ///
-    /// - You are at beat 9.0 (phase 1.0) and force beat 0.0 (phase 0.0) at `now`
-    /// - Your local beat becomes 0.0, the session phase reference shifts by -1.0
-    /// - A peer at beat 109.0 (phase 1.0) becomes beat 108.0 (phase 0.0)
-    ///
-    /// The peer's magnitude changed by -1.0 to match the new session phase.
-    /// This causes a beat discontinuity—the peer's beat counter jumps.
-    ///
-    /// **Warning:** This is anti-social behavior. Only use this for bridging an
-    /// external clock source into a Link session. Most applications should use
-    /// [`request_beat_at_time`](Self::request_beat_at_time) instead.
-    ///
-    /// Changes are local to this snapshot until committed with
-    /// [`Link::commit_app_session_state`] or [`AudioLink::commit_session_state`].
-    ///
-    /// # Arguments
-    ///
-    /// * `beat` - The beat to map (determines the new session phase).
-    /// * `time` - The time to map it to.
-    /// * `quantum` - The quantum (beats per cycle/bar).
-    pub fn force_beat_at_time(&mut self, beat: f64, time: Instant, quantum: f64) {
-        // Safety: handle is valid (checked in new()).
-        unsafe {
-            sys::abl_link_force_beat_at_time(self.handle, beat, time.as_u64(), quantum);
-        }
-    }
-
-    /// Get the current transport state.
-    ///
-    /// This is part of the transport sync feature. Enable it via
-    /// [`Link::enable_transport_sync`] to share state with peers.
-    ///
-    /// The returned state indicates the *target* transport state, which may
-    /// already be in effect or scheduled for the future. Use
-    /// [`transport_state_time`](Self::transport_state_time) to determine when
-    /// the state took/takes effect.
-    ///
-    /// # Returns
-    ///
-    /// - [`TransportState::Play`] if transport is playing or scheduled to play.
-    /// - [`TransportState::Stop`] if transport is stopped or scheduled to stop.
-    #[must_use]
-    pub fn transport_state(&self) -> TransportState {
-        // Safety: handle is valid (checked in new()).
-        unsafe { sys::abl_link_is_playing(self.handle) }.into()
-    }
-
-    /// Start transport at the specified time.
-    ///
-    /// This is part of the transport sync feature. Enable it via
-    /// [`Link::enable_transport_sync`] to share state with peers.
-    ///
-    /// Changes are local to this snapshot until committed with
-    /// [`Link::commit_app_session_state`] or [`AudioLink::commit_session_state`].
-    ///
-    /// # Arguments
-    ///
-    /// * `time` - The time at which playback starts.
-    pub fn start_transport_at(&mut self, time: Instant) {
-        self.set_transport_state_at(TransportState::Play, time);
-    }
-
-    /// Stop transport at the specified time.
-    ///
-    /// This is part of the transport sync feature. Enable it via
-    /// [`Link::enable_transport_sync`] to share state with peers.
-    ///
-    /// Changes are local to this snapshot until committed with
-    /// [`Link::commit_app_session_state`] or [`AudioLink::commit_session_state`].
-    ///
-    /// # Arguments
-    ///
-    /// * `time` - The time at which playback stops.
-    pub fn stop_transport_at(&mut self, time: Instant) {
-        self.set_transport_state_at(TransportState::Stop, time);
-    }
-
-    /// Set the transport state at the specified time.
-    ///
-    /// This is part of the transport sync feature. Enable it via
-    /// [`Link::enable_transport_sync`] to share state with peers. The change
-    /// takes effect at the specified time.
-    ///
-    /// This is useful when the transport state comes from a variable.
-    /// For static start/stop, prefer [`start_transport_at`](Self::start_transport_at)
-    /// and [`stop_transport_at`](Self::stop_transport_at) for readability.
-    ///
-    /// Changes are local to this snapshot until committed with
-    /// [`Link::commit_app_session_state`] or [`AudioLink::commit_session_state`].
-    ///
-    /// # Arguments
-    ///
-    /// * `state` - The desired transport state.
-    /// * `time` - The time at which the change takes effect.
-    pub fn set_transport_state_at(&mut self, state: TransportState, time: Instant) {
-        // Safety: handle is valid (checked in new()).
-        unsafe { sys::abl_link_set_is_playing(self.handle, state.into(), time.as_u64()) }
-    }
-
-    /// Get the time associated with the current transport state.
-    ///
-    /// Use this in combination with [`transport_state`](Self::transport_state)
-    /// to determine whether the transport state is currently active or scheduled:
-    ///
-    /// - **Time in the past** (< `clock_now()`): The state from `transport_state()`
-    ///   is currently in effect.
-    /// - **Time in the future** (> `clock_now()`): The state from `transport_state()`
-    ///   is scheduled to take effect at this time.
-    ///
-    /// The meaning of this time also depends on whether the transport state has
-    /// been modified in this session state snapshot:
-    ///
-    /// - **Before any local modifications**: This is the time at which the
-    ///   current transport state (playing or stopped) took effect or is
-    ///   scheduled to take effect.
-    ///
-    /// - **After calling [`set_transport_state_at`] (or [`start_transport_at`]/
-    ///   [`stop_transport_at`])**: This returns the `at_time` you provided.
-    ///
-    /// If no transport state has ever been set, returns a time of 0.
-    ///
-    /// [`set_transport_state_at`]: Self::set_transport_state_at
-    /// [`start_transport_at`]: Self::start_transport_at
-    /// [`stop_transport_at`]: Self::stop_transport_at
-    #[must_use]
-    pub fn transport_state_time(&self) -> Instant {
-        // Safety: handle is valid (checked in new()).
-        Instant(unsafe { sys::abl_link_time_for_is_playing(self.handle) }.cast_signed())
-    }
-
-    /// Request to map the given beat to the transport state time.
-    ///
-    /// This calls [`request_beat_at_time`](Self::request_beat_at_time) with
-    /// the time from [`transport_state_time`](Self::transport_state_time).
-    ///
-    /// This is useful for quantized launch scenarios where you want the beat
-    /// at the transport start time to be a specific value (e.g., 0.0 for the
-    /// beginning of a song).
-    ///
-    /// **Note:** This is a no-op if transport is stopped
-    /// ([`transport_state`](Self::transport_state) returns [`TransportState::Stop`]).
-    ///
-    /// Changes are local to this snapshot until committed with
-    /// [`Link::commit_app_session_state`] or [`AudioLink::commit_session_state`].
-    ///
-    /// # Arguments
-    ///
-    /// * `beat` - The beat to map to the transport state time.
-    /// * `quantum` - The quantum (beats per cycle/bar).
-    pub fn request_beat_at_transport_state_time(&mut self, beat: f64, quantum: f64) {
-        // Safety: handle is valid (checked in new()).
-        unsafe { sys::abl_link_request_beat_at_start_playing_time(self.handle, beat, quantum) }
-    }
-
-    /// Start transport and request a beat mapping in one operation.
-    ///
-    /// This is equivalent to calling [`start_transport_at`] followed by
-    /// [`request_beat_at_transport_state_time`]. It starts transport at the
-    /// given time and maps the given beat to that time.
-    ///
-    /// Changes are local to this snapshot until committed with
-    /// [`Link::commit_app_session_state`] or [`AudioLink::commit_session_state`].
-    ///
-    /// # Arguments
-    ///
-    /// * `beat` - The beat to map to the start time.
-    /// * `time` - The time at which transport starts.
-    /// * `quantum` - The quantum (beats per cycle/bar).
-    ///
-    /// [`start_transport_at`]: Self::start_transport_at
-    /// [`request_beat_at_transport_state_time`]: Self::request_beat_at_transport_state_time
-    pub fn start_transport_and_request_beat_at(
-        &mut self,
-        beat: f64,
-        time: Instant,
-        quantum: f64,
-    ) {
-        // Safety: handle is valid (checked in new()).
-        unsafe {
-            sys::abl_link_set_is_playing_and_request_beat_at_time(
-                self.handle,
-                true,
-                time.as_u64(),
-                beat,
-                quantum,
-            );
-        }
+    pub fn poll_event(&self) -> Option<LinkEvent> {
+        self.link.event_queue.poll()
     }
 }
 
-impl Drop for SessionState {
-    fn drop(&mut self) {
-        // Safety: handle is valid (checked in new()).
-        unsafe { sys::abl_link_destroy_session_state(self.handle) }
-    }
-}
@@ -0,0 +1,262 @@
+//! Drives external MIDI hardware from the Link timeline via MIDI Beat Clock
+//! and the MIDI real-time transport messages.
+
+use crate::{ClockBridge, Instant, PhaseAlignment, SessionState, TransportState};
+
+/// MIDI Beat Clock: 24 pulses per quarter-note beat.
+const CLOCK_PPQN: f64 = 24.0;
+
+/// One MIDI-beat (as used by Song Position Pointer) is a sixteenth note,
+/// i.e. six clock ticks, so there are four MIDI-beats per quarter-note beat.
+const MIDI_BEATS_PER_BEAT: f64 = CLOCK_PPQN / 6.0;
+
+/// Timing Clock: sent 24 times per quarter note.
+pub const CLOCK: u8 = 0xF8;
+/// Start: begin playback from song position 0.
+pub const START: u8 = 0xFA;
+/// Continue: resume playback from the current song position.
+pub const CONTINUE: u8 = 0xFB;
+/// Stop: stop playback.
+pub const STOP: u8 = 0xFC;
+/// Active Sensing: sent periodically to detect a dropped MIDI connection.
+pub const ACTIVE_SENSING: u8 = 0xFE;
+/// System Reset: return to power-up default state.
+pub const SYSTEM_RESET: u8 = 0xFF;
+/// Song Position Pointer: followed by two 7-bit data bytes (LSB first).
+pub const SONG_POSITION_POINTER: u8 = 0xF2;
+
+/// Converts a synchronized Link timeline into MIDI Beat Clock and transport
+/// messages, so an ESP32 can drive external MIDI hardware over UART.
+///
+/// `MidiClockBridge` is pull-based: call [`update`](Self::update) on every
+/// tick of your own scheduling loop (ideally a high-frequency `esp_timer`
+/// callback, so jitter stays low) with a freshly captured session state and
+/// the current time. It computes which MIDI Beat Clock pulses
+/// ([`CLOCK`]) are now due from [`SessionState::beat_at_time`] and emits
+/// them via `sink`, so it naturally tracks tempo changes without drift.
+/// A transition of [`SessionState::transport_state`] to `Play` is translated
+/// to a Song Position Pointer ([`SONG_POSITION_POINTER`]), derived from the
+/// transport start beat and quantum, followed by [`START`] or [`CONTINUE`];
+/// a transition to `Stop` is translated to [`STOP`] alone.
+///
+/// ```no_run
+/// use esp_idf_ableton_link::Link;
+/// use esp_idf_ableton_link::midi::MidiClockBridge;
+///
+/// let link = Link::new(120.0).unwrap();
+/// let mut bridge = MidiClockBridge::new(4.0); // 4/4 time
+///
+/// // On each tick of your scheduling loop:
+/// let state = link.capture_app_session_state().unwrap();
+/// bridge.update(&state, link.clock_now(), |byte| {
+///     // uart.write(&[byte]);
+/// });
+/// ```
+pub struct MidiClockBridge {
+    quantum: f64,
+    last_tick: Option<i64>,
+    last_transport: Option<TransportState>,
+}
+
+impl MidiClockBridge {
+    /// Create a new bridge for the given quantum (beats per bar).
+    #[must_use]
+    pub const fn new(quantum: f64) -> Self {
+        Self {
+            quantum,
+            last_tick: None,
+            last_transport: None,
+        }
+    }
+
+    /// Advance the bridge to `now`, emitting via `sink` every MIDI real-time
+    /// byte that is now due: any transport message ([`START`]/[`CONTINUE`]
+    /// with its preceding Song Position Pointer, or [`STOP`]), followed by
+    /// any [`CLOCK`] pulses.
+    pub fn update(&mut self, state: &SessionState, now: Instant, mut sink: impl FnMut(u8)) {
+        self.emit_transport(state, &mut sink);
+        self.emit_clock(state, now, &mut sink);
+    }
+
+    fn emit_transport(&mut self, state: &SessionState, sink: &mut impl FnMut(u8)) {
+        let transport = state.transport_state();
+        let prev = self.last_transport.replace(transport);
+        if prev == Some(transport) {
+            return;
+        }
+
+        match transport {
+            TransportState::Play => {
+                let beat = state.transport_state_beat(self.quantum).max(0.0);
+                let midi_beat = (beat * MIDI_BEATS_PER_BEAT).round() as u16 & 0x3FFF;
+
+                sink(SONG_POSITION_POINTER);
+                sink((midi_beat & 0x7F) as u8);
+                sink(((midi_beat >> 7) & 0x7F) as u8);
+                sink(if midi_beat == 0 { START } else { CONTINUE });
+
+                // Realign clock ticks to the position we just announced, so
+                // the jump in song position doesn't cause a burst of
+                // catch-up clock pulses.
+                self.last_tick = Some((beat * CLOCK_PPQN).floor() as i64);
+            }
+            TransportState::Stop => sink(STOP),
+        }
+    }
+
+    fn emit_clock(&mut self, state: &SessionState, now: Instant, sink: &mut impl FnMut(u8)) {
+        let tick = (state.beat_at_time(now, self.quantum) * CLOCK_PPQN).floor() as i64;
+
+        let Some(last_tick) = self.last_tick else {
+            self.last_tick = Some(tick);
+            return;
+        };
+
+        for _ in last_tick..tick {
+            sink(CLOCK);
+        }
+        self.last_tick = Some(tick);
+    }
+}
+
+/// Parser state while a Song Position Pointer's two data bytes are arriving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SongPositionState {
+    #[default]
+    None,
+    /// [`SONG_POSITION_POINTER`] seen, waiting for the LSB data byte.
+    WaitingLsb,
+    /// LSB received, waiting for the MSB data byte.
+    WaitingMsb(u8),
+}
+
+/// Drives a Link session from an incoming external MIDI clock: MIDI Beat
+/// Clock pulses recover tempo, and the MIDI real-time transport messages
+/// plus Song Position Pointer drive Link's transport and beat mapping.
+///
+/// This is the inverse of [`MidiClockBridge`], which turns a Link timeline
+/// *into* outgoing MIDI Beat Clock. `MidiClockFollower` turns an *incoming*
+/// one into updates applied via [`SessionState::force_beat_at_time`] —
+/// explicitly documented as the tool for "bridging an external clock source
+/// into a Link session," the way a DAW like Ardour slaves its transport to
+/// an external master clock. Because it forces the beat mapping, overriding
+/// every peer's session phase, run at most one follower per session.
+///
+/// Tempo recovery from [`CLOCK`] pulses is delegated to [`ClockBridge`],
+/// configured for [`PhaseAlignment::Force`] so beat alignment matches the
+/// transport/Song Position Pointer handling below.
+///
+/// ```no_run
+/// use esp_idf_ableton_link::Link;
+/// use esp_idf_ableton_link::midi::MidiClockFollower;
+///
+/// let mut link = Link::new(120.0).unwrap();
+/// let mut follower = MidiClockFollower::new(4.0); // 4/4 time
+///
+/// // For each incoming MIDI byte (e.g. from uart.read()):
+/// let byte = 0xF8;
+/// let mut state = link.capture_app_session_state().unwrap();
+/// follower.handle_byte(byte, link.clock_now(), &mut state, |state| {
+///     link.commit_app_session_state(state);
+/// });
+/// ```
+pub struct MidiClockFollower {
+    quantum: f64,
+    clock: ClockBridge,
+    pulse_index: u64,
+    song_position: SongPositionState,
+}
+
+impl MidiClockFollower {
+    /// Create a new follower for the given quantum (beats per bar), used
+    /// both for phase alignment and for mapping Song Position Pointer
+    /// sixteenth-notes onto Link beats.
+    #[must_use]
+    pub fn new(quantum: f64) -> Self {
+        Self {
+            quantum,
+            clock: ClockBridge::new(CLOCK_PPQN, quantum).with_phase_alignment(PhaseAlignment::Force),
+            pulse_index: 0,
+            song_position: SongPositionState::None,
+        }
+    }
+
+    /// Feed one incoming MIDI byte.
+    ///
+    /// Recognizes [`CLOCK`], [`START`], [`CONTINUE`], [`STOP`], and
+    /// [`SONG_POSITION_POINTER`] (plus its two following data bytes); the
+    /// rest of the System Real-Time category ([`ACTIVE_SENSING`],
+    /// [`SYSTEM_RESET`], and the two reserved/undefined status bytes) is
+    /// consumed without effect, since those bytes may legally interrupt an
+    /// in-progress Song Position Pointer; any other byte is ignored.
+    /// `commit` is called with `session` whenever a byte produces an update
+    /// that should be committed (e.g. via
+    /// [`Link::commit_app_session_state`](crate::Link::commit_app_session_state)
+    /// or [`AudioLink::commit_session_state`](crate::AudioLink::commit_session_state)).
+    pub fn handle_byte(
+        &mut self,
+        byte: u8,
+        time: Instant,
+        session: &mut SessionState,
+        commit: impl FnOnce(&SessionState),
+    ) {
+        // Real-Time status bytes may legally appear interleaved anywhere in
+        // the stream, including between a Song Position Pointer's LSB and
+        // MSB data bytes, without disturbing the message they interrupt.
+        // Dispatch them first, independent of `song_position`'s state, so a
+        // `CLOCK` arriving mid-SPP is never mistaken for SPP data (which
+        // would both corrupt the pending position and drop the tick).
+        match byte {
+            CLOCK => {
+                self.pulse_index += 1;
+                self.clock.pulse(self.pulse_index, time, session, commit);
+                return;
+            }
+            START => {
+                self.pulse_index = 0;
+                self.clock.reset();
+                session.force_beat_at_time(0.0, time, self.quantum);
+                session.start_transport_at(time);
+                commit(session);
+                return;
+            }
+            CONTINUE => {
+                session.start_transport_at(time);
+                commit(session);
+                return;
+            }
+            STOP => {
+                session.stop_transport_at(time);
+                commit(session);
+                return;
+            }
+            // The rest of the System Real-Time category (0xF8-0xFF): Active
+            // Sensing, System Reset, and the two reserved/undefined status
+            // bytes. None of them carry data or affect Link, but they must
+            // still be consumed here rather than falling through, or they'd
+            // be wrongly treated as SPP LSB/MSB data below.
+            byte if byte >= 0xF8 => return,
+            _ => {}
+        }
+
+        match self.song_position {
+            SongPositionState::WaitingLsb => {
+                self.song_position = SongPositionState::WaitingMsb(byte);
+                return;
+            }
+            SongPositionState::WaitingMsb(lsb) => {
+                self.song_position = SongPositionState::None;
+                let midi_beats = f64::from((u16::from(byte) << 7) | u16::from(lsb));
+                let beat = midi_beats / MIDI_BEATS_PER_BEAT;
+                session.force_beat_at_time(beat, time, self.quantum);
+                commit(session);
+                return;
+            }
+            SongPositionState::None => {}
+        }
+
+        if byte == SONG_POSITION_POINTER {
+            self.song_position = SongPositionState::WaitingLsb;
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Bars:beats:ticks musical-position layer on top of the raw beat value.
+
+/// A musical position expressed as bar, beat, and tick, following the
+/// bars:beats:ticks convention used by sequencers such as Ardour.
+///
+/// `bar` and `beat` are zero-indexed counts of complete bars and beats from
+/// the timeline's origin (which, per peer, is arbitrary — see
+/// [`beat_at_time`](crate::SessionState::beat_at_time)). `tick` subdivides
+/// the current beat into [`ppqn`](Self::DEFAULT_PPQN) equal parts.
+///
+/// Use [`SessionState::bar_beat_tick_at_time`](crate::SessionState::bar_beat_tick_at_time)
+/// and [`SessionState::time_at_bar_beat_tick`](crate::SessionState::time_at_bar_beat_tick)
+/// to convert to and from the Link timeline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BarBeatTick {
+    /// The bar number, zero-indexed from the timeline's origin.
+    pub bar: i64,
+    /// The beat within the bar, in `0..quantum as i64`.
+    pub beat: i64,
+    /// The tick within the beat, in `0..ppqn`.
+    pub tick: i64,
+}
+
+impl BarBeatTick {
+    /// The default ticks-per-beat (PPQN) resolution, a common sequencer
+    /// tick resolution (finer than MIDI Beat Clock's 24 PPQN).
+    pub const DEFAULT_PPQN: i64 = 960;
+
+    /// Convert a raw Link beat value into a bar/beat/tick position.
+    ///
+    /// `quantum` is the number of beats per bar; `ppqn` is the number of
+    /// ticks per beat. Handles negative beats (e.g. during a count-in) by
+    /// flooring rather than truncating, so bar and beat stay correct across
+    /// beat zero.
+    #[must_use]
+    pub fn from_beat(beat: f64, quantum: f64, ppqn: i64) -> Self {
+        let bar = (beat / quantum).floor();
+        let beat_in_bar = beat - bar * quantum;
+        let mut beat_int = beat_in_bar.floor();
+        let frac = beat_in_bar - beat_int;
+
+        let mut tick = (frac * ppqn as f64).round() as i64;
+        if tick >= ppqn {
+            tick -= ppqn;
+            beat_int += 1.0;
+        }
+
+        let mut bar = bar as i64;
+        let mut beat_int = beat_int as i64;
+        if beat_int as f64 >= quantum {
+            beat_int = 0;
+            bar += 1;
+        }
+
+        Self {
+            bar,
+            beat: beat_int,
+            tick,
+        }
+    }
+
+    /// Convert this bar/beat/tick position back into a raw Link beat value.
+    ///
+    /// This is the inverse of [`from_beat`](Self::from_beat), given the same
+    /// `quantum` and `ppqn`.
+    #[must_use]
+    pub fn to_beat(self, quantum: f64, ppqn: i64) -> f64 {
+        self.bar as f64 * quantum + self.beat as f64 + self.tick as f64 / ppqn as f64
+    }
+}
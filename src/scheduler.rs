@@ -0,0 +1,222 @@
+//! A beat/phase-aligned event scheduler for [`Link`], backed by ESP-IDF's
+//! `esp_timer`.
+//!
+//! This turns the manual "compute a delay, feed it to a timer" dance shown
+//! in the [module documentation](crate#triggering-on-the-downbeat) into a
+//! real API: [`Link::schedule_at_beat`], [`Link::schedule_next_downbeat`],
+//! and [`Link::schedule_every_beats`].
+
+use std::ffi::c_void;
+
+use crate::{Link, LinkError};
+
+mod timer_sys {
+    pub use esp_idf_sys::{
+        esp_timer_create, esp_timer_create_args_t, esp_timer_delete,
+        esp_timer_dispatch_t_ESP_TIMER_TASK, esp_timer_handle_t, esp_timer_start_once,
+        esp_timer_stop,
+    };
+}
+
+// Internal per-event state. Heap-allocated (inside the returned
+// `ScheduledEvent`'s `Box`) so its address stays stable across firings,
+// since the C timer only ever sees a raw pointer to it.
+struct ScheduleState<'a> {
+    link: &'a Link,
+    quantum: f64,
+    repeat_interval_beats: Option<f64>,
+    next_beat: f64,
+    callback: Box<dyn FnMut() + Send>,
+    timer: timer_sys::esp_timer_handle_t,
+}
+
+/// A handle to an event scheduled with [`Link::schedule_at_beat`],
+/// [`Link::schedule_next_downbeat`], or [`Link::schedule_every_beats`].
+///
+/// Dropping this stops and deletes the underlying `esp_timer`, cancelling
+/// any further firings.
+pub struct ScheduledEvent<'a> {
+    state: Box<ScheduleState<'a>>,
+}
+
+impl Drop for ScheduledEvent<'_> {
+    fn drop(&mut self) {
+        // Safety: the timer was created successfully in `schedule` and has
+        // not yet been deleted.
+        unsafe {
+            timer_sys::esp_timer_stop(self.state.timer);
+            timer_sys::esp_timer_delete(self.state.timer);
+        }
+    }
+}
+
+// Re-arm the one-shot timer for `state.next_beat`, mapping it to an
+// `Instant` against a freshly captured session state so tempo changes and
+// peer re-synchronization are reflected in the delay.
+//
+// # Errors
+//
+// Returns [`LinkError::AllocationFailed`] if the session state could not be
+// captured, or [`LinkError::TimerError`] if `esp_timer_start_once` failed.
+fn arm(state: &mut ScheduleState) -> Result<(), LinkError> {
+    let session = state.link.capture_app_session_state()?;
+    let now = state.link.clock_now();
+    let target = session.time_at_beat(state.next_beat, state.quantum);
+    let delay_us = (target - now).as_micros().max(0).cast_unsigned();
+
+    // Safety: `state.timer` is a valid handle for the lifetime of `state`.
+    let err = unsafe { timer_sys::esp_timer_start_once(state.timer, delay_us) };
+    if err != 0 {
+        return Err(LinkError::TimerError(err));
+    }
+    Ok(())
+}
+
+extern "C" fn fire(arg: *mut c_void) {
+    // Catch panics to prevent unwinding across the FFI boundary.
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        // Safety: `arg` is the stable address of the `ScheduleState` boxed
+        // inside the corresponding `ScheduledEvent`, which outlives the timer.
+        let state = unsafe { &mut *arg.cast::<ScheduleState<'_>>() };
+
+        (state.callback)();
+
+        if let Some(interval) = state.repeat_interval_beats {
+            state.next_beat += interval;
+            // There's no `Result` to return from an `esp_timer` callback, so
+            // a failure here (e.g. a transient capture failure) can only be
+            // surfaced as a log: the repeat silently stops firing otherwise,
+            // with no way for the caller to notice.
+            if let Err(err) = arm(state) {
+                log::warn!("esp_idf_ableton_link: failed to re-arm scheduled event: {err}");
+            }
+        }
+    }));
+}
+
+impl Link {
+    /// Schedule `callback` to run once, near the time the given `beat`
+    /// occurs for `quantum`.
+    ///
+    /// Internally this captures a session state, maps `beat` to an
+    /// [`Instant`](crate::Instant) via
+    /// [`SessionState::time_at_beat`](crate::SessionState::time_at_beat), and
+    /// arms an `esp_timer` for the delay until then.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LinkError::AllocationFailed`] if the session state could
+    /// not be captured, or [`LinkError::TimerError`] if the underlying
+    /// `esp_timer` could not be created or started.
+    pub fn schedule_at_beat<F>(
+        &self,
+        beat: f64,
+        quantum: f64,
+        callback: F,
+    ) -> Result<ScheduledEvent<'_>, LinkError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.schedule(beat, quantum, None, callback)
+    }
+
+    /// Schedule `callback` to run once, at the next downbeat (the start of
+    /// the next `quantum`-beat cycle) after the current time.
+    ///
+    /// # Errors
+    ///
+    /// See [`schedule_at_beat`](Self::schedule_at_beat).
+    pub fn schedule_next_downbeat<F>(
+        &self,
+        quantum: f64,
+        callback: F,
+    ) -> Result<ScheduledEvent<'_>, LinkError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let session = self.capture_app_session_state()?;
+        let now = self.clock_now();
+        let current_beat = session.beat_at_time(now, quantum);
+        let current_phase = session.phase_at_time(now, quantum);
+        let next_downbeat_beat = current_beat + (quantum - current_phase);
+
+        self.schedule(next_downbeat_beat, quantum, None, callback)
+    }
+
+    /// Schedule `callback` to run every `interval_beats` beats, starting
+    /// `interval_beats` beats after the current beat. Firings are spaced
+    /// from that start, not aligned to multiples of `interval_beats`.
+    ///
+    /// Each firing recomputes its delay against a freshly captured session
+    /// state, so tempo changes and peer re-synchronization adjust future
+    /// firings rather than accumulating drift.
+    ///
+    /// # Errors
+    ///
+    /// See [`schedule_at_beat`](Self::schedule_at_beat).
+    pub fn schedule_every_beats<F>(
+        &self,
+        interval_beats: f64,
+        quantum: f64,
+        callback: F,
+    ) -> Result<ScheduledEvent<'_>, LinkError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let session = self.capture_app_session_state()?;
+        let now = self.clock_now();
+        let next_beat = session.beat_at_time(now, quantum) + interval_beats;
+
+        self.schedule(next_beat, quantum, Some(interval_beats), callback)
+    }
+
+    fn schedule<F>(
+        &self,
+        beat: f64,
+        quantum: f64,
+        repeat_interval_beats: Option<f64>,
+        callback: F,
+    ) -> Result<ScheduledEvent<'_>, LinkError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let mut state = Box::new(ScheduleState {
+            link: self,
+            quantum,
+            repeat_interval_beats,
+            next_beat: beat,
+            callback: Box::new(callback),
+            timer: std::ptr::null_mut(),
+        });
+
+        let create_args = timer_sys::esp_timer_create_args_t {
+            callback: Some(fire),
+            arg: std::ptr::from_mut(state.as_mut()).cast::<c_void>(),
+            dispatch_method: timer_sys::esp_timer_dispatch_t_ESP_TIMER_TASK,
+            name: c"esp_idf_ableton_link_schedule".as_ptr(),
+            skip_unhandled_events: false,
+        };
+
+        // Safety: create_args is only used for the duration of this call,
+        // as required by esp_timer_create.
+        let err = unsafe { timer_sys::esp_timer_create(&create_args, &mut state.timer) };
+        if err != 0 {
+            return Err(LinkError::TimerError(err));
+        }
+
+        if let Err(err) = arm(state.as_mut()) {
+            // The timer was already created above; without this it would
+            // leak, since `state` has no `Drop` of its own — only
+            // `ScheduledEvent`, which we're about to fail to construct, stops
+            // and deletes it.
+            //
+            // Safety: `state.timer` was just created successfully above and
+            // has not been started (the failed `arm` call is what would have
+            // started it).
+            unsafe { timer_sys::esp_timer_delete(state.timer) };
+            return Err(err);
+        }
+
+        Ok(ScheduledEvent { state })
+    }
+}
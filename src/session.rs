@@ -1,7 +1,7 @@
 //! Session state for Link synchronization.
 
 use crate::time::Instant;
-use crate::TransportState;
+use crate::{BarBeatTick, LinkError, Timeline, TransportState};
 
 mod sys {
     #[allow(clippy::wildcard_imports)]
@@ -46,7 +46,74 @@ pub struct SessionState {
 // intent is for session state to be used in a local scope after capture.
 unsafe impl Send for SessionState {}
 
+impl Clone for SessionState {
+    /// Branch off an independent copy of this snapshot.
+    ///
+    /// The clone gets its own handle (via `abl_link_create_session_state`)
+    /// with the same tempo, beat/time mapping, and transport state copied
+    /// over. It is useful for "what-if" computation: clone a captured state,
+    /// apply a speculative [`set_tempo`](Self::set_tempo) or
+    /// [`request_beat_at_time`](Self::request_beat_at_time) to the clone to
+    /// inspect the resulting [`beat_at_time`](Self::beat_at_time) or
+    /// [`phase_at_time`](Self::phase_at_time), and discard it without
+    /// touching the state you intend to commit.
+    ///
+    /// # Quantum
+    ///
+    /// Internally this pivots through [`to_timeline`](Self::to_timeline)/
+    /// [`apply_timeline`](Self::apply_timeline) at quantum `1.0`, since
+    /// `SessionState` doesn't remember the quantum you actually use it with.
+    /// `Timeline`'s own beat mapping is quantum-independent once anchored
+    /// (see its docs), so this reproduces tempo and transport state exactly
+    /// — but if you read [`beat_at_time`](Self::beat_at_time)/
+    /// [`phase_at_time`](Self::phase_at_time) at a quantum other than `1.0`,
+    /// do the round trip yourself with [`to_timeline`](Self::to_timeline)/
+    /// [`apply_timeline`](Self::apply_timeline) and your real quantum
+    /// instead of cloning.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying session state could not be allocated.
+    fn clone(&self) -> Self {
+        let mut clone = Self::new().expect("failed to allocate session state");
+
+        let anchor = self.transport_state_time();
+        clone.apply_timeline(&self.to_timeline(anchor, 1.0), 1.0);
+
+        clone
+    }
+}
+
 impl SessionState {
+    /// Create a new, unattached `SessionState`.
+    ///
+    /// Unlike [`Link::capture_app_session_state`](crate::Link::capture_app_session_state)
+    /// or [`AudioLink::capture_session_state`](crate::AudioLink::capture_session_state),
+    /// this builds a fresh timeline/transport snapshot with no connection to a
+    /// live `Link` session. This is useful for authoring a session state
+    /// offline — set tempo and beat mappings, then commit it once a `Link` is
+    /// available — and for unit tests that don't need an active `Link`. It
+    /// also lets an application save and restore its desired local
+    /// start/stop state across restarts without racing a capture: Link
+    /// documents that a peer's local start/stop state persists across
+    /// joining and leaving a session, so authoring that state before the
+    /// first capture is a supported pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LinkError::AllocationFailed`] if the underlying session state
+    /// could not be allocated.
+    pub fn new() -> Result<Self, LinkError> {
+        // Safety: abl_link_create_session_state allocates a new session state.
+        let handle = unsafe { sys::abl_link_create_session_state() };
+
+        if handle.impl_.is_null() {
+            Err(LinkError::AllocationFailed)
+        } else {
+            Ok(Self::from_handle(handle))
+        }
+    }
+
     /// Create a new `SessionState` from a raw handle.
     ///
     /// # Safety
@@ -136,6 +203,80 @@ impl SessionState {
         Instant::from_micros(unsafe { sys::abl_link_time_at_beat(self.handle, beat, quantum) })
     }
 
+    /// Get the bar/beat/tick musical position at the given time.
+    ///
+    /// This layers [`BarBeatTick`] — the positional format used by
+    /// sequencers like Ardour — on top of [`beat_at_time`](Self::beat_at_time).
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The time (from [`Link::clock_now`](crate::Link::clock_now)).
+    /// * `quantum` - The quantum (beats per bar).
+    /// * `ppqn` - Ticks per beat (see [`BarBeatTick::DEFAULT_PPQN`]).
+    #[must_use]
+    pub fn bar_beat_tick_at_time(&self, time: Instant, quantum: f64, ppqn: i64) -> BarBeatTick {
+        BarBeatTick::from_beat(self.beat_at_time(time, quantum), quantum, ppqn)
+    }
+
+    /// Get the time at which the given bar/beat/tick position occurs.
+    ///
+    /// This is the inverse of [`bar_beat_tick_at_time`](Self::bar_beat_tick_at_time),
+    /// assuming constant tempo.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - The bar/beat/tick position.
+    /// * `quantum` - The quantum (beats per bar).
+    /// * `ppqn` - Ticks per beat (see [`BarBeatTick::DEFAULT_PPQN`]).
+    #[must_use]
+    pub fn time_at_bar_beat_tick(&self, pos: BarBeatTick, quantum: f64, ppqn: i64) -> Instant {
+        self.time_at_beat(pos.to_beat(quantum, ppqn), quantum)
+    }
+
+    /// Capture this session's timeline as a plain-data [`Timeline`]
+    /// snapshot, anchored at `now`.
+    ///
+    /// Unlike `SessionState` itself, the returned [`Timeline`] has no
+    /// connection to this handle and never becomes stale — see its
+    /// documentation for why that's safe.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The time to anchor the snapshot at (from [`Link::clock_now`](crate::Link::clock_now)).
+    /// * `quantum` - The quantum (beats per cycle/bar) used to compute the anchor beat.
+    #[must_use]
+    pub fn to_timeline(&self, now: Instant, quantum: f64) -> Timeline {
+        Timeline {
+            tempo: self.tempo(),
+            anchor_time: now,
+            anchor_beat: self.beat_at_time(now, quantum),
+            transport: self.transport_state(),
+            transport_time: self.transport_state_time(),
+        }
+    }
+
+    /// Apply a previously captured [`Timeline`] to this session state.
+    ///
+    /// Reconstructs the tempo and beat mapping via
+    /// [`set_tempo`](Self::set_tempo) and
+    /// [`request_beat_at_time`](Self::request_beat_at_time) (pivoting on the
+    /// timeline's anchor point), and the transport state via
+    /// [`set_transport_state_at`](Self::set_transport_state_at).
+    ///
+    /// Changes are local to this snapshot until committed with
+    /// [`Link::commit_app_session_state`](crate::Link::commit_app_session_state) or
+    /// [`AudioLink::commit_session_state`](crate::AudioLink::commit_session_state).
+    ///
+    /// # Arguments
+    ///
+    /// * `timeline` - The timeline to apply.
+    /// * `quantum` - The quantum (beats per cycle/bar) the timeline was captured with.
+    pub fn apply_timeline(&mut self, timeline: &Timeline, quantum: f64) {
+        self.set_tempo(timeline.tempo, timeline.anchor_time);
+        self.request_beat_at_time(timeline.anchor_beat, timeline.anchor_time, quantum);
+        self.set_transport_state_at(timeline.transport, timeline.transport_time);
+    }
+
     /// Request a beat/time mapping, respecting session phase when not alone
     /// in the session (quantized launch).
     ///
@@ -337,6 +478,36 @@ impl SessionState {
         )
     }
 
+    /// Get the beat at which the current transport state takes/took effect.
+    ///
+    /// This is equivalent to
+    /// `beat_at_time(self.transport_state_time(), quantum)`, letting callers
+    /// scheduling a quantized start learn which beat the downbeat lands on
+    /// without manually threading [`transport_state_time`](Self::transport_state_time)
+    /// back through [`beat_at_time`](Self::beat_at_time) every frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `quantum` - The quantum (beats per cycle/bar).
+    #[must_use]
+    pub fn transport_state_beat(&self, quantum: f64) -> f64 {
+        self.beat_at_time(self.transport_state_time(), quantum)
+    }
+
+    /// Get the phase at which the current transport state takes/took effect.
+    ///
+    /// This is equivalent to
+    /// `phase_at_time(self.transport_state_time(), quantum)`. See
+    /// [`transport_state_beat`](Self::transport_state_beat).
+    ///
+    /// # Arguments
+    ///
+    /// * `quantum` - The quantum (beats per cycle/bar).
+    #[must_use]
+    pub fn transport_state_phase(&self, quantum: f64) -> f64 {
+        self.phase_at_time(self.transport_state_time(), quantum)
+    }
+
     /// Request to map the given beat to the transport state time.
     ///
     /// This calls [`request_beat_at_time`](Self::request_beat_at_time) with
@@ -392,6 +563,108 @@ impl SessionState {
             );
         }
     }
+
+    /// Get the beat at a given sample offset within an audio block.
+    ///
+    /// Block-based audio callbacks (as on ESP-IDF) only know their buffer's
+    /// start time and a frame offset within it, not a ready-made `Instant`
+    /// for every sample. This converts `sample_offset` frames at
+    /// `sample_rate` to microseconds, adds them to `frame_time`, and calls
+    /// [`beat_at_time`](Self::beat_at_time) — keeping the drift-sensitive
+    /// sample/microsecond conversion in one place.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame_time` - The [`Instant`] at the start of the audio block.
+    /// * `sample_offset` - The frame offset within the block.
+    /// * `sample_rate` - The sample rate, in Hz.
+    /// * `quantum` - The quantum (beats per cycle/bar).
+    #[must_use]
+    pub fn beat_at_sample(
+        &self,
+        frame_time: Instant,
+        sample_offset: u32,
+        sample_rate: u32,
+        quantum: f64,
+    ) -> f64 {
+        let offset_micros = i64::from(sample_offset) * 1_000_000 / i64::from(sample_rate);
+        self.beat_at_time(frame_time.add_micros(offset_micros), quantum)
+    }
+
+    /// Iterate over the whole-beat boundaries falling within an audio block,
+    /// yielding each one's sample offset within the block.
+    ///
+    /// This is the inverse of [`beat_at_sample`](Self::beat_at_sample): it
+    /// locates beat events (for a click, metronome, or scheduled action) to
+    /// exact frame positions within a buffer of `frames` samples starting at
+    /// `buffer_start`, the way a sample-based DAW transport (e.g. Ardour's)
+    /// locates its own events.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_start` - The [`Instant`] at the start of the audio block.
+    /// * `frames` - The number of frames in the block.
+    /// * `sample_rate` - The sample rate, in Hz.
+    /// * `quantum` - The quantum (beats per cycle/bar).
+    #[must_use]
+    pub fn beats_in_buffer(
+        &self,
+        buffer_start: Instant,
+        frames: u32,
+        sample_rate: u32,
+        quantum: f64,
+    ) -> BeatsInBuffer<'_> {
+        let next_beat = self.beat_at_time(buffer_start, quantum).ceil();
+        BeatsInBuffer {
+            session: self,
+            quantum,
+            buffer_start,
+            frames,
+            sample_rate,
+            next_beat,
+        }
+    }
+}
+
+/// Iterator over whole-beat boundaries within an audio block, returned by
+/// [`SessionState::beats_in_buffer`].
+pub struct BeatsInBuffer<'a> {
+    session: &'a SessionState,
+    quantum: f64,
+    buffer_start: Instant,
+    frames: u32,
+    sample_rate: u32,
+    next_beat: f64,
+}
+
+impl Iterator for BeatsInBuffer<'_> {
+    /// The sample offset, within the block, of a beat boundary.
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let beat_time = self.session.time_at_beat(self.next_beat, self.quantum);
+        let offset_micros = (beat_time - self.buffer_start).as_micros();
+        let offset_samples = offset_micros * i64::from(self.sample_rate) / 1_000_000;
+
+        if offset_samples < 0 || offset_samples >= i64::from(self.frames) {
+            return None;
+        }
+
+        self.next_beat += 1.0;
+        // Safety of the cast: bounds-checked above against `self.frames: u32`.
+        Some(offset_samples as u32)
+    }
+}
+
+impl Default for SessionState {
+    /// Create a new `SessionState`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying session state could not be allocated.
+    fn default() -> Self {
+        Self::new().expect("failed to allocate session state")
+    }
 }
 
 impl Drop for SessionState {
@@ -0,0 +1,160 @@
+//! Channel- and `Stream`-based subscription API layered over the
+//! `set_*_callback` primitives.
+//!
+//! Every non-trivial consumer of [`Link::set_tempo_callback`] and friends
+//! ends up hand-rolling an [`mpsc::channel`] inside the callback closure to
+//! forward events elsewhere. [`Link::peer_count_stream`], [`Link::tempo_stream`],
+//! [`Link::transport_stream`], and the multiplexed [`Link::events`] build
+//! that plumbing in: the callback setters remain the low-level primitive,
+//! and these are a thin layer on top. The channels are unbounded: nothing
+//! limits how many undelivered events, or how much memory, they hold.
+
+use std::sync::mpsc;
+
+use crate::{Link, LinkEvent, TransportState};
+
+impl Link {
+    /// Subscribe to peer-count changes as a channel of values.
+    ///
+    /// Internally this installs a [`set_num_peers_callback`](Self::set_num_peers_callback)
+    /// that forwards each new count to the returned [`Receiver`](mpsc::Receiver),
+    /// replacing any callback previously installed via that method or via
+    /// [`enable_event_queue`](Self::enable_event_queue).
+    #[must_use]
+    pub fn peer_count_stream(&self) -> mpsc::Receiver<u64> {
+        let (tx, rx) = mpsc::channel();
+        self.set_num_peers_callback(move |num_peers| {
+            let _ = tx.send(num_peers);
+        });
+        rx
+    }
+
+    /// Subscribe to tempo changes as a channel of values.
+    ///
+    /// See [`peer_count_stream`](Self::peer_count_stream) for the general
+    /// shape; this installs [`set_tempo_callback`](Self::set_tempo_callback)
+    /// instead.
+    #[must_use]
+    pub fn tempo_stream(&self) -> mpsc::Receiver<f64> {
+        let (tx, rx) = mpsc::channel();
+        self.set_tempo_callback(move |tempo| {
+            let _ = tx.send(tempo);
+        });
+        rx
+    }
+
+    /// Subscribe to transport state changes as a channel of values.
+    ///
+    /// See [`peer_count_stream`](Self::peer_count_stream) for the general
+    /// shape; this installs
+    /// [`set_transport_state_callback`](Self::set_transport_state_callback)
+    /// instead.
+    #[must_use]
+    pub fn transport_stream(&self) -> mpsc::Receiver<TransportState> {
+        let (tx, rx) = mpsc::channel();
+        self.set_transport_state_callback(move |state| {
+            let _ = tx.send(state);
+        });
+        rx
+    }
+
+    /// Subscribe to peer-count, tempo, and transport changes on a single,
+    /// multiplexed channel of [`LinkEvent`].
+    ///
+    /// This installs all three `set_*_callback` primitives at once, each
+    /// forwarding into the same channel tagged by [`LinkEvent`] variant, so a
+    /// single consumer loop can react to any of them. It replaces any
+    /// callback previously installed via the individual `set_*_callback`
+    /// methods, the single-value `*_stream` methods above, or
+    /// [`enable_event_queue`](Self::enable_event_queue); prefer the latter
+    /// instead if the consumer is a realtime audio thread, since this
+    /// channel can allocate on send.
+    #[must_use]
+    pub fn events(&self) -> mpsc::Receiver<LinkEvent> {
+        let (tx, rx) = mpsc::channel();
+
+        let peer_tx = tx.clone();
+        self.set_num_peers_callback(move |num_peers| {
+            let _ = peer_tx.send(LinkEvent::PeerCount(num_peers));
+        });
+
+        let tempo_tx = tx.clone();
+        self.set_tempo_callback(move |tempo| {
+            let _ = tempo_tx.send(LinkEvent::Tempo(tempo));
+        });
+
+        self.set_transport_state_callback(move |state| {
+            let _ = tx.send(LinkEvent::Transport(state));
+        });
+
+        rx
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_stream_adapters {
+    use futures_core::Stream;
+    use tokio::sync::mpsc::unbounded_channel;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    use super::Link;
+    use crate::{LinkEvent, TransportState};
+
+    impl Link {
+        /// Async adapter over [`peer_count_stream`](Link::peer_count_stream)
+        /// for callers in an async context.
+        #[must_use]
+        pub fn peer_count_async_stream(&self) -> impl Stream<Item = u64> {
+            let (tx, rx) = unbounded_channel();
+            self.set_num_peers_callback(move |num_peers| {
+                let _ = tx.send(num_peers);
+            });
+            UnboundedReceiverStream::new(rx)
+        }
+
+        /// Async adapter over [`tempo_stream`](Link::tempo_stream) for
+        /// callers in an async context.
+        #[must_use]
+        pub fn tempo_async_stream(&self) -> impl Stream<Item = f64> {
+            let (tx, rx) = unbounded_channel();
+            self.set_tempo_callback(move |tempo| {
+                let _ = tx.send(tempo);
+            });
+            UnboundedReceiverStream::new(rx)
+        }
+
+        /// Async adapter over [`transport_stream`](Link::transport_stream)
+        /// for callers in an async context.
+        #[must_use]
+        pub fn transport_async_stream(&self) -> impl Stream<Item = TransportState> {
+            let (tx, rx) = unbounded_channel();
+            self.set_transport_state_callback(move |state| {
+                let _ = tx.send(state);
+            });
+            UnboundedReceiverStream::new(rx)
+        }
+
+        /// Async adapter over [`events`](Link::events) for callers in an
+        /// async context.
+        #[must_use]
+        pub fn events_async_stream(&self) -> impl Stream<Item = LinkEvent> {
+            let (tx, rx) = unbounded_channel();
+
+            let peer_tx = tx.clone();
+            self.set_num_peers_callback(move |num_peers| {
+                let _ = peer_tx.send(LinkEvent::PeerCount(num_peers));
+            });
+
+            let tempo_tx = tx.clone();
+            self.set_tempo_callback(move |tempo| {
+                let _ = tempo_tx.send(LinkEvent::Tempo(tempo));
+            });
+
+            self.set_transport_state_callback(move |state| {
+                let _ = tx.send(LinkEvent::Transport(state));
+            });
+
+            UnboundedReceiverStream::new(rx)
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Gradual tempo automation (accelerandi/ritardandi) on top of the
+//! instantaneous [`SessionState::set_tempo`].
+
+use crate::{Duration, Instant, SessionState};
+
+/// The interpolation curve a [`TempoRamp`] uses between its start and
+/// target tempo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant rate of change (the default).
+    #[default]
+    Linear,
+    /// Starts slow, accelerates towards the target.
+    EaseIn,
+    /// Starts fast, decelerates into the target.
+    EaseOut,
+    /// Starts slow, speeds up through the middle, then slows into the
+    /// target.
+    EaseInOut,
+    /// Exponential accelerando/ritardando: most of the change happens in
+    /// the final stretch of the ramp, the way a DAW's "exponential" tempo
+    /// automation curve feels more dramatic near the target than a linear
+    /// ramp.
+    Exponential,
+}
+
+impl Easing {
+    /// Apply the curve to a progress value in `[0, 1]`, returning a value in
+    /// the same range.
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            Self::Exponential => {
+                if t <= 0.0 {
+                    0.0
+                } else {
+                    2.0_f64.powf(10.0 * (t - 1.0))
+                }
+            }
+        }
+    }
+}
+
+/// The lowest tempo (in BPM) Ableton Link accepts; [`TempoRamp::bpm_at`]
+/// clamps to this floor.
+pub const MIN_BPM: f64 = 20.0;
+/// The highest tempo (in BPM) Ableton Link accepts; [`TempoRamp::bpm_at`]
+/// clamps to this ceiling.
+pub const MAX_BPM: f64 = 999.0;
+
+/// A tempo automation from a start tempo to a target tempo over a fixed
+/// duration.
+///
+/// `TempoRamp` itself never touches a [`SessionState`] on its own; the
+/// caller drives it by recapturing a session state on each tick and calling
+/// [`apply_to`](Self::apply_to), which sets the interpolated tempo with the
+/// current time as the pivot so the beat at that time is preserved, then
+/// committing the state:
+///
+/// ```no_run
+/// use esp_idf_ableton_link::{Duration, Link, TempoRamp};
+///
+/// let mut link = Link::new(120.0).unwrap();
+/// let ramp = TempoRamp::new(120.0, 140.0, link.clock_now(), Duration::from_secs(8));
+///
+/// // On each tick, e.g. driven by a timer:
+/// let now = link.clock_now();
+/// let mut state = link.capture_app_session_state().unwrap();
+/// ramp.apply_to(&mut state, now);
+/// link.commit_app_session_state(&state);
+/// ```
+///
+/// [`bpm_at`](Self::bpm_at) snaps exactly to the target tempo once `now` is
+/// at or past the end of the ramp, so repeated application past completion
+/// doesn't accumulate rounding error.
+#[derive(Debug, Clone, Copy)]
+pub struct TempoRamp {
+    start_bpm: f64,
+    target_bpm: f64,
+    start_time: Instant,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl TempoRamp {
+    /// Create a tempo ramp from `start_bpm` to `target_bpm`, running from
+    /// `start_time` for `duration`.
+    #[must_use]
+    pub fn new(start_bpm: f64, target_bpm: f64, start_time: Instant, duration: Duration) -> Self {
+        Self {
+            start_bpm,
+            target_bpm,
+            start_time,
+            duration,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Use the given easing curve instead of the default linear one.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Progress through the ramp at `now`, clamped to `[0, 1]`.
+    fn progress(&self, now: Instant) -> f64 {
+        if self.duration <= Duration::ZERO {
+            return 1.0;
+        }
+        let elapsed = now - self.start_time;
+        (elapsed.as_micros() as f64 / self.duration.as_micros() as f64).clamp(0.0, 1.0)
+    }
+
+    /// Get the interpolated tempo at the given time, clamped to
+    /// [`MIN_BPM`]/[`MAX_BPM`].
+    ///
+    /// Returns `target_bpm` exactly once `now` is at or past the end of the
+    /// ramp.
+    #[must_use]
+    pub fn bpm_at(&self, now: Instant) -> f64 {
+        let t = self.progress(now);
+        let bpm = if t >= 1.0 {
+            self.target_bpm
+        } else {
+            self.start_bpm + (self.target_bpm - self.start_bpm) * self.easing.apply(t)
+        };
+        bpm.clamp(MIN_BPM, MAX_BPM)
+    }
+
+    /// Returns `true` once `now` is at or past the end of the ramp.
+    #[must_use]
+    pub fn is_complete(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+
+    /// Apply the interpolated tempo at `now` to `state`, using `now` as the
+    /// pivot point so the beat at `now` is preserved.
+    ///
+    /// Changes are local to `state` until committed, same as
+    /// [`set_tempo`](SessionState::set_tempo).
+    pub fn apply_to(&self, state: &mut SessionState, now: Instant) {
+        state.set_tempo(self.bpm_at(now), now);
+    }
+}
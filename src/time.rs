@@ -4,6 +4,7 @@
 //! to the Link clock, which is synchronized across all connected peers.
 
 use std::{
+    fmt,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
     time::Duration as StdDuration,
 };
@@ -62,6 +63,7 @@ use std::{
 /// let later = now + Duration::from_millis(100);
 /// ```
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Instant(i64);
 
 impl Instant {
@@ -88,17 +90,43 @@ impl Instant {
     }
 
     /// Add microseconds to this time.
+    ///
+    /// Panics on debug-assertion overflow and wraps silently in release; use
+    /// [`checked_add_micros`](Self::checked_add_micros) if `micros` isn't
+    /// known to be small.
     #[must_use]
     pub const fn add_micros(self, micros: i64) -> Self {
         Self(self.0 + micros)
     }
 
     /// Subtract microseconds from this time.
+    ///
+    /// Panics on debug-assertion overflow and wraps silently in release; use
+    /// [`checked_sub_micros`](Self::checked_sub_micros) if `micros` isn't
+    /// known to be small.
     #[must_use]
     pub const fn sub_micros(self, micros: i64) -> Self {
         Self(self.0 - micros)
     }
 
+    /// Add microseconds to this time, returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_add_micros(self, micros: i64) -> Option<Self> {
+        match self.0.checked_add(micros) {
+            Some(result) => Some(Self(result)),
+            None => None,
+        }
+    }
+
+    /// Subtract microseconds from this time, returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_sub_micros(self, micros: i64) -> Option<Self> {
+        match self.0.checked_sub(micros) {
+            Some(result) => Some(Self(result)),
+            None => None,
+        }
+    }
+
     /// Add milliseconds to this time.
     #[must_use]
     pub const fn add_millis(self, millis: i64) -> Self {
@@ -122,6 +150,36 @@ impl Instant {
     pub const fn sub_secs(self, secs: i64) -> Self {
         Self(self.0 - secs * 1_000_000)
     }
+
+    /// Add a [`Duration`], returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_add(self, rhs: Duration) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(micros) => Some(Self(micros)),
+            None => None,
+        }
+    }
+
+    /// Subtract a [`Duration`], returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Duration) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(micros) => Some(Self(micros)),
+            None => None,
+        }
+    }
+
+    /// Add a [`Duration`], clamping to `i64::MIN`/`i64::MAX` on overflow.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Duration) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Subtract a [`Duration`], clamping to `i64::MIN`/`i64::MAX` on overflow.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Duration) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
 }
 
 /// A duration of time in microseconds, for use with [`Instant`].
@@ -166,17 +224,55 @@ impl Duration {
     }
 
     /// Create a `Duration` from milliseconds.
+    ///
+    /// `millis * 1_000` panics on debug-assertion overflow and wraps
+    /// silently in release for very large inputs; use
+    /// [`checked_from_millis`](Self::checked_from_millis) if that matters.
     #[must_use]
     pub const fn from_millis(millis: i64) -> Self {
         Self(millis * 1_000)
     }
 
     /// Create a `Duration` from seconds.
+    ///
+    /// `secs * 1_000_000` panics on debug-assertion overflow and wraps
+    /// silently in release for very large inputs; use
+    /// [`checked_from_secs`](Self::checked_from_secs) if that matters.
     #[must_use]
     pub const fn from_secs(secs: i64) -> Self {
         Self(secs * 1_000_000)
     }
 
+    /// Create a `Duration` from milliseconds, returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_from_millis(millis: i64) -> Option<Self> {
+        match millis.checked_mul(1_000) {
+            Some(micros) => Some(Self(micros)),
+            None => None,
+        }
+    }
+
+    /// Create a `Duration` from seconds, returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_from_secs(secs: i64) -> Option<Self> {
+        match secs.checked_mul(1_000_000) {
+            Some(micros) => Some(Self(micros)),
+            None => None,
+        }
+    }
+
+    /// Create a `Duration` from a fractional number of seconds.
+    #[must_use]
+    pub fn from_secs_f64(secs: f64) -> Self {
+        Self((secs * 1_000_000.0) as i64)
+    }
+
+    /// Create a `Duration` from a fractional number of milliseconds.
+    #[must_use]
+    pub fn from_millis_f64(millis: f64) -> Self {
+        Self((millis * 1_000.0) as i64)
+    }
+
     /// Get the duration as microseconds.
     #[must_use]
     pub const fn as_micros(self) -> i64 {
@@ -195,11 +291,159 @@ impl Duration {
         self.0 / 1_000_000
     }
 
+    /// Get the duration as a fractional number of seconds.
+    #[must_use]
+    pub fn as_secs_f64(self) -> f64 {
+        self.0 as f64 / 1_000_000.0
+    }
+
+    /// Get the duration as a fractional number of milliseconds.
+    #[must_use]
+    pub fn as_millis_f64(self) -> f64 {
+        self.0 as f64 / 1_000.0
+    }
+
+    /// Get the integer seconds component of this duration, truncating
+    /// towards zero (matching [`as_secs`](Self::as_secs)).
+    #[must_use]
+    pub const fn secs(self) -> i64 {
+        self.as_secs()
+    }
+
+    /// Get the milliseconds remaining after [`secs`](Self::secs) is removed,
+    /// in `0..1_000` (or `-999..=0` for a negative duration).
+    #[must_use]
+    pub const fn millis(self) -> i64 {
+        (self.0 % 1_000_000) / 1_000
+    }
+
+    /// Get the microseconds remaining after [`secs`](Self::secs) and
+    /// [`millis`](Self::millis) are removed, in `0..1_000` (or `-999..=0`
+    /// for a negative duration).
+    #[must_use]
+    pub const fn micros(self) -> i64 {
+        self.0 % 1_000
+    }
+
     /// Returns the absolute value of this duration.
     #[must_use]
     pub const fn abs(self) -> Self {
         Self(self.0.abs())
     }
+
+    /// Add another `Duration`, returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(micros) => Some(Self(micros)),
+            None => None,
+        }
+    }
+
+    /// Subtract another `Duration`, returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(micros) => Some(Self(micros)),
+            None => None,
+        }
+    }
+
+    /// Multiply by an `i64`, returning `None` on overflow.
+    #[must_use]
+    pub const fn checked_mul(self, rhs: i64) -> Option<Self> {
+        match self.0.checked_mul(rhs) {
+            Some(micros) => Some(Self(micros)),
+            None => None,
+        }
+    }
+
+    /// Add another `Duration`, clamping to `i64::MIN`/`i64::MAX` on overflow.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Subtract another `Duration`, clamping to `i64::MIN`/`i64::MAX` on overflow.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Multiply by an `i64`, clamping to `i64::MIN`/`i64::MAX` on overflow.
+    #[must_use]
+    pub const fn saturating_mul(self, rhs: i64) -> Self {
+        Self(self.0.saturating_mul(rhs))
+    }
+}
+
+/// Error returned by [`TryFrom<Duration>`](TryFrom) for
+/// [`std::time::Duration`] when the source `Duration` is negative.
+///
+/// [`std::time::Duration`](https://doc.rust-lang.org/std/time/struct.Duration.html) is
+/// unsigned, so a negative Link `Duration` cannot be represented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromDurationError(());
+
+impl fmt::Display for TryFromDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot convert a negative Duration to std::time::Duration")
+    }
+}
+
+impl std::error::Error for TryFromDurationError {}
+
+impl TryFrom<Duration> for StdDuration {
+    type Error = TryFromDurationError;
+
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        let micros = u64::try_from(duration.0).map_err(|_| TryFromDurationError(()))?;
+        Ok(Self::from_micros(micros))
+    }
+}
+
+impl From<StdDuration> for Duration {
+    /// Converts a [`std::time::Duration`], clamping to `i64::MAX` if it
+    /// doesn't fit in a signed microsecond count — the same clamping
+    /// convention used by the `Instant + StdDuration` impls.
+    fn from(duration: StdDuration) -> Self {
+        Self(i64::try_from(duration.as_micros()).unwrap_or(i64::MAX))
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let micros = self.0;
+        let sign = if micros < 0 { "-" } else { "" };
+        let abs_micros = micros.unsigned_abs();
+
+        if abs_micros >= 1_000_000 {
+            write!(
+                f,
+                "{sign}{}.{:06}s",
+                abs_micros / 1_000_000,
+                abs_micros % 1_000_000
+            )
+        } else if abs_micros >= 1_000 {
+            write!(
+                f,
+                "{sign}{}.{:03}ms",
+                abs_micros / 1_000,
+                abs_micros % 1_000
+            )
+        } else {
+            write!(f, "{sign}{abs_micros}\u{b5}s")
+        }
+    }
+}
+
+impl fmt::Display for Instant {
+    /// Prints the elapsed time since the Link clock's origin, scaled the
+    /// same way as [`Duration`]'s `Display` impl (e.g. `1.234567s`,
+    /// `12.500ms`, `500µs`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Duration(self.0), f)
+    }
 }
 
 impl Add for Duration {
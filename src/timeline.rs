@@ -0,0 +1,73 @@
+//! A plain-data, serializable snapshot of a session's beat/time mapping,
+//! decoupled from the live C handle.
+
+use crate::{Instant, TransportState};
+
+/// A linear beat/time mapping plus tempo and transport state, captured from
+/// a [`SessionState`](crate::SessionState) at a point in time.
+///
+/// [`SessionState`](crate::SessionState) is explicitly documented as a
+/// snapshot that "will become stale" and shouldn't be stored. The underlying
+/// timeline it represents, though, is just a linear beat/time mapping plus
+/// tempo — the same shape the C++ `toSessionState`/`toIncomingClientState`
+/// conversions use. `Timeline` is that plain-data value, captured via
+/// [`SessionState::to_timeline`](crate::SessionState::to_timeline). With the
+/// anchor point captured, [`beat_at_time`](Self::beat_at_time),
+/// [`phase_at_time`](Self::phase_at_time), and
+/// [`time_at_beat`](Self::time_at_beat) reproduce the same computation with
+/// no FFI call and no staleness hazard, so a `Timeline` is safe to log, send
+/// over your own transport, or hold across an ESP32 restart — unlike a
+/// `SessionState`.
+///
+/// Round-trip a previously captured `Timeline` back into a live session with
+/// [`SessionState::apply_timeline`](crate::SessionState::apply_timeline).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timeline {
+    /// Tempo in Beats Per Minute, constant across the timeline.
+    pub tempo: f64,
+    /// The time of the anchor point.
+    pub anchor_time: Instant,
+    /// The beat value at the anchor point ([`anchor_time`](Self::anchor_time)).
+    pub anchor_beat: f64,
+    /// The transport state.
+    pub transport: TransportState,
+    /// The time at which [`transport`](Self::transport) took/takes effect.
+    pub transport_time: Instant,
+}
+
+impl Timeline {
+    /// Get the beat value at the given time.
+    ///
+    /// Computed directly from the anchor point, with no FFI call. See
+    /// [`SessionState::beat_at_time`](crate::SessionState::beat_at_time) for
+    /// the semantics this reproduces.
+    #[must_use]
+    pub fn beat_at_time(&self, time: Instant) -> f64 {
+        let elapsed_micros = (time - self.anchor_time).as_micros() as f64;
+        self.anchor_beat + elapsed_micros * self.tempo / 60_000_000.0
+    }
+
+    /// Get the phase (position within a cycle) at the given time, in the
+    /// interval `[0, quantum)`.
+    ///
+    /// Equivalent to `beat_at_time(time) % quantum` for non-negative beat
+    /// values, but handles negative values correctly. See
+    /// [`SessionState::phase_at_time`](crate::SessionState::phase_at_time).
+    #[must_use]
+    pub fn phase_at_time(&self, time: Instant, quantum: f64) -> f64 {
+        self.beat_at_time(time).rem_euclid(quantum)
+    }
+
+    /// Get the time at which the given beat occurs.
+    ///
+    /// Inverse of [`beat_at_time`](Self::beat_at_time), assuming constant
+    /// tempo. See
+    /// [`SessionState::time_at_beat`](crate::SessionState::time_at_beat).
+    #[must_use]
+    pub fn time_at_beat(&self, beat: f64) -> Instant {
+        let elapsed_beats = beat - self.anchor_beat;
+        let elapsed_micros = (elapsed_beats * 60_000_000.0 / self.tempo) as i64;
+        self.anchor_time.add_micros(elapsed_micros)
+    }
+}